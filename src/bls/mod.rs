@@ -0,0 +1,362 @@
+//! BLS signatures over BLS12-381 with verifying keys in G1 and signatures in G2,
+//! using the proof-of-possession ciphersuite of the
+//! [IETF BLS signature draft](https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05#section-4.2.3).
+//!
+//! Verifying keys must only be aggregated after their proof of possession
+//! has been checked with [verify_possession], otherwise aggregate verification
+//! is vulnerable to rogue-key attacks.
+
+use blst::{
+    min_pk::{AggregatePublicKey, AggregateSignature, PublicKey, SecretKey, Signature},
+    BLST_ERROR,
+};
+use rand::RngCore;
+use tracing::error;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{
+    constants::{BLS_TAG, KEYGEN_TAG},
+    crypto_tools::{message_digest::MessageDigest, rng},
+    sdk::{
+        api::{BytesVec, TofnError, TofnResult},
+        key::SecretRecoveryKey,
+    },
+};
+
+pub const VERIFYING_KEY_LENGTH: usize = 48;
+
+/// Domain separation tags for hashing to G2
+const SIGN_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+const POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+#[derive(ZeroizeOnDrop)]
+pub struct KeyPair(SecretKey);
+
+/// `SecretKey`'s own `Debug` impl prints the secret, so only the verifying key is displayed.
+impl core::fmt::Debug for KeyPair {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KeyPair")
+            .field("encoded_verifying_key", &self.encoded_verifying_key())
+            .finish_non_exhaustive()
+    }
+}
+
+impl KeyPair {
+    /// Compressed G1 point.
+    pub fn encoded_verifying_key(&self) -> [u8; VERIFYING_KEY_LENGTH] {
+        self.0.sk_to_pk().to_bytes()
+    }
+}
+
+pub fn keygen(
+    secret_recovery_key: &SecretRecoveryKey,
+    session_nonce: &[u8],
+) -> TofnResult<KeyPair> {
+    let mut rng =
+        rng::rng_seed_signing_key(BLS_TAG, KEYGEN_TAG, secret_recovery_key, session_nonce)?;
+
+    let mut ikm = [0; 32];
+    rng.fill_bytes(&mut ikm);
+
+    let signing_key = SecretKey::key_gen(&ikm, &[]);
+
+    ikm.zeroize();
+
+    Ok(KeyPair(signing_key.map_err(|err| {
+        error!("failure to generate bls signing key: {:?}", err);
+        TofnError::Internal {
+            reason: "failure to generate signing key",
+        }
+    })?))
+}
+
+/// Returns a BLS signature encoded as a compressed 96-byte G2 point.
+pub fn sign(signing_key: &KeyPair, message_digest: &MessageDigest) -> TofnResult<BytesVec> {
+    Ok(signing_key
+        .0
+        .sign(message_digest.as_ref(), SIGN_DST, &[])
+        .to_bytes()
+        .into())
+}
+
+pub fn verify(
+    encoded_verifying_key: &[u8; VERIFYING_KEY_LENGTH],
+    message_digest: &MessageDigest,
+    encoded_signature: &[u8],
+) -> TofnResult<bool> {
+    let verifying_key = decode_verifying_key(encoded_verifying_key)?;
+    let signature =
+        Signature::from_bytes(encoded_signature).map_err(|_| TofnError::InvalidEncoding {
+            kind: "bls signature",
+        })?;
+
+    Ok(signature.verify(
+        true,
+        message_digest.as_ref(),
+        SIGN_DST,
+        &[],
+        &verifying_key,
+        false,
+    ) == BLST_ERROR::BLST_SUCCESS)
+}
+
+/// Returns a proof that the caller holds the signing key for its verifying key.
+pub fn prove_possession(signing_key: &KeyPair) -> BytesVec {
+    signing_key
+        .0
+        .sign(&signing_key.encoded_verifying_key(), POP_DST, &[])
+        .to_bytes()
+        .into()
+}
+
+pub fn verify_possession(
+    encoded_verifying_key: &[u8; VERIFYING_KEY_LENGTH],
+    encoded_proof: &[u8],
+) -> TofnResult<bool> {
+    let verifying_key = decode_verifying_key(encoded_verifying_key)?;
+    let proof = Signature::from_bytes(encoded_proof).map_err(|_| TofnError::InvalidEncoding {
+        kind: "bls proof of possession",
+    })?;
+
+    Ok(proof.verify(
+        true,
+        encoded_verifying_key,
+        POP_DST,
+        &[],
+        &verifying_key,
+        false,
+    ) == BLST_ERROR::BLST_SUCCESS)
+}
+
+/// Combine signatures on the same message digest into a single signature.
+pub fn aggregate_signatures(encoded_signatures: &[&[u8]]) -> TofnResult<BytesVec> {
+    if encoded_signatures.is_empty() {
+        error!("no bls signatures to aggregate");
+        return Err(TofnError::InvalidArgument {
+            reason: "no signatures to aggregate",
+        });
+    }
+
+    let signatures = encoded_signatures
+        .iter()
+        .map(|s| {
+            Signature::from_bytes(s).map_err(|_| TofnError::InvalidEncoding {
+                kind: "bls signature",
+            })
+        })
+        .collect::<TofnResult<Vec<_>>>()?;
+
+    let aggregate = AggregateSignature::aggregate(&signatures.iter().collect::<Vec<_>>(), true)
+        .map_err(|err| {
+            error!("failure to aggregate bls signatures: {:?}", err);
+            TofnError::Internal {
+                reason: "failure to aggregate signatures",
+            }
+        })?;
+
+    Ok(aggregate.to_signature().to_bytes().into())
+}
+
+/// Combine verifying keys into a single verifying key.
+/// Each key's proof of possession must have been verified beforehand.
+pub fn aggregate_verifying_keys(
+    encoded_verifying_keys: &[[u8; VERIFYING_KEY_LENGTH]],
+) -> TofnResult<[u8; VERIFYING_KEY_LENGTH]> {
+    if encoded_verifying_keys.is_empty() {
+        error!("no bls verifying keys to aggregate");
+        return Err(TofnError::InvalidArgument {
+            reason: "no verifying keys to aggregate",
+        });
+    }
+
+    let verifying_keys = encoded_verifying_keys
+        .iter()
+        .map(decode_verifying_key)
+        .collect::<TofnResult<Vec<_>>>()?;
+
+    let aggregate =
+        AggregatePublicKey::aggregate(&verifying_keys.iter().collect::<Vec<_>>(), false).map_err(
+            |err| {
+                error!("failure to aggregate bls verifying keys: {:?}", err);
+                TofnError::Internal {
+                    reason: "failure to aggregate verifying keys",
+                }
+            },
+        )?;
+
+    Ok(aggregate.to_public_key().to_bytes())
+}
+
+/// Verify an aggregate signature on `message_digest` from all of `encoded_verifying_keys`.
+/// Each key's proof of possession must have been verified beforehand.
+pub fn verify_aggregate(
+    encoded_verifying_keys: &[[u8; VERIFYING_KEY_LENGTH]],
+    message_digest: &MessageDigest,
+    encoded_signature: &[u8],
+) -> TofnResult<bool> {
+    verify(
+        &aggregate_verifying_keys(encoded_verifying_keys)?,
+        message_digest,
+        encoded_signature,
+    )
+}
+
+/// Decode a compressed G1 point, rejecting the identity and points outside the prime-order subgroup.
+fn decode_verifying_key(
+    encoded_verifying_key: &[u8; VERIFYING_KEY_LENGTH],
+) -> TofnResult<PublicKey> {
+    PublicKey::key_validate(encoded_verifying_key).map_err(|_| TofnError::InvalidEncoding {
+        kind: "bls verifying key",
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        aggregate_signatures, aggregate_verifying_keys, keygen, prove_possession, sign, verify,
+        verify_aggregate, verify_possession,
+    };
+    use crate::sdk::{
+        api::TofnError,
+        key::{dummy_secret_recovery_key, SecretRecoveryKey},
+    };
+
+    #[test]
+    fn keygen_sign_decode_verify() {
+        let message_digest = [42; 32].into();
+
+        let key_pair = keygen(&dummy_secret_recovery_key(42), b"tofn nonce").unwrap();
+        let encoded_signature = sign(&key_pair, &message_digest).unwrap();
+
+        // Correct signature should verify
+        let success = verify(
+            &key_pair.encoded_verifying_key(),
+            &message_digest,
+            &encoded_signature,
+        )
+        .unwrap();
+
+        assert!(success);
+
+        // Signature on a different message should not verify
+        let success = verify(
+            &key_pair.encoded_verifying_key(),
+            &[43; 32].into(),
+            &encoded_signature,
+        )
+        .unwrap();
+
+        assert!(!success);
+    }
+
+    #[test]
+    fn possession() {
+        let key_pair = keygen(&dummy_secret_recovery_key(0), b"tofn nonce").unwrap();
+        let other_key_pair = keygen(&dummy_secret_recovery_key(1), b"tofn nonce").unwrap();
+
+        let proof = prove_possession(&key_pair);
+        assert!(verify_possession(&key_pair.encoded_verifying_key(), &proof).unwrap());
+        assert!(!verify_possession(&other_key_pair.encoded_verifying_key(), &proof).unwrap());
+
+        // a signature on a message is not a valid proof
+        let signature = sign(&key_pair, &[42; 32].into()).unwrap();
+        assert!(!verify_possession(&key_pair.encoded_verifying_key(), &signature).unwrap());
+    }
+
+    #[test]
+    fn aggregate_sign_verify() {
+        let message_digest = [42; 32].into();
+
+        let key_pairs: Vec<_> = (0..3)
+            .map(|i| keygen(&dummy_secret_recovery_key(i), b"tofn nonce").unwrap())
+            .collect();
+        let encoded_verifying_keys: Vec<_> = key_pairs
+            .iter()
+            .map(|k| k.encoded_verifying_key())
+            .collect();
+        let signatures: Vec<_> = key_pairs
+            .iter()
+            .map(|k| sign(k, &message_digest).unwrap())
+            .collect();
+
+        let aggregate =
+            aggregate_signatures(&signatures.iter().map(|s| s.as_slice()).collect::<Vec<_>>())
+                .unwrap();
+
+        assert!(verify_aggregate(&encoded_verifying_keys, &message_digest, &aggregate).unwrap());
+        assert!(verify(
+            &aggregate_verifying_keys(&encoded_verifying_keys).unwrap(),
+            &message_digest,
+            &aggregate
+        )
+        .unwrap());
+
+        // missing signer
+        assert!(
+            !verify_aggregate(&encoded_verifying_keys[..2], &message_digest, &aggregate).unwrap()
+        );
+
+        assert_eq!(
+            aggregate_signatures(&[]),
+            Err(TofnError::InvalidArgument {
+                reason: "no signatures to aggregate"
+            })
+        );
+        assert_eq!(
+            aggregate_verifying_keys(&[]),
+            Err(TofnError::InvalidArgument {
+                reason: "no verifying keys to aggregate"
+            })
+        );
+    }
+
+    /// Check keygen/signing outputs against golden files to catch regressions (such as on updating deps).
+    #[test]
+    fn keygen_sign_known_vectors() {
+        struct TestCase {
+            secret_recovery_key: SecretRecoveryKey,
+            session_nonce: Vec<u8>,
+            message_digest: [u8; 32],
+        }
+
+        let test_cases = vec![
+            TestCase {
+                secret_recovery_key: SecretRecoveryKey([0; 64]),
+                session_nonce: vec![0; 4],
+                message_digest: [42; 32],
+            },
+            TestCase {
+                secret_recovery_key: SecretRecoveryKey([0xff; 64]),
+                session_nonce: vec![0xff; 32],
+                message_digest: [0xff; 32],
+            },
+        ];
+
+        let expected_outputs: Vec<Vec<_>> = test_cases
+            .into_iter()
+            .map(|test_case| {
+                let keypair =
+                    keygen(&test_case.secret_recovery_key, &test_case.session_nonce).unwrap();
+                let encoded_signing_key = keypair.0.to_bytes().to_vec();
+                let encoded_verifying_key = keypair.encoded_verifying_key().to_vec();
+
+                let signature = sign(&keypair, &test_case.message_digest.into()).unwrap();
+
+                let success = verify(
+                    &keypair.encoded_verifying_key(),
+                    &test_case.message_digest.into(),
+                    &signature,
+                )
+                .unwrap();
+                assert!(success);
+
+                [encoded_signing_key, encoded_verifying_key, signature]
+                    .into_iter()
+                    .map(hex::encode)
+                    .collect()
+            })
+            .collect();
+
+        goldie::assert_json!(expected_outputs);
+    }
+}
@@ -1,5 +1,5 @@
+use core::{marker::PhantomData, num::TryFromIntError, str::FromStr};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
-use std::marker::PhantomData;
 use zeroize::Zeroize;
 
 pub struct TypedUsize<K>(usize, PhantomData<K>);
@@ -38,14 +38,14 @@ impl<K> Clone for TypedUsize<K> {
     }
 }
 
-impl<K> std::fmt::Debug for TypedUsize<K> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<K> core::fmt::Debug for TypedUsize<K> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.0.fmt(f)
     }
 }
 
-impl<K> std::fmt::Display for TypedUsize<K> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<K> core::fmt::Display for TypedUsize<K> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         self.0.fmt(f)
     }
 }
@@ -56,6 +56,37 @@ impl<K> PartialEq for TypedUsize<K> {
     }
 }
 
+impl<K> From<usize> for TypedUsize<K> {
+    fn from(index: usize) -> Self {
+        Self::from_usize(index)
+    }
+}
+
+impl<K> From<TypedUsize<K>> for usize {
+    fn from(index: TypedUsize<K>) -> Self {
+        index.0
+    }
+}
+
+/// Inverse of the platform-independent [TypedUsize::to_bytes] encoding,
+/// which can fail on platforms where `usize` is narrower than `u64`.
+impl<K> TryFrom<u64> for TypedUsize<K> {
+    type Error = TryFromIntError;
+
+    fn try_from(index: u64) -> Result<Self, Self::Error> {
+        Ok(Self::from_usize(index.try_into()?))
+    }
+}
+
+/// Parses the decimal format produced by `Display`.
+impl<K> FromStr for TypedUsize<K> {
+    type Err = <usize as FromStr>::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from_usize(s.parse()?))
+    }
+}
+
 impl<K> Serialize for TypedUsize<K> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -77,12 +108,14 @@ impl<'de, K> Deserialize<'de> for TypedUsize<K> {
 #[cfg(test)]
 mod tests {
     use super::TypedUsize;
-    use crate::sdk::wire_bytes::{deserialize, serialize};
 
     struct TestMarker;
 
+    #[cfg(feature = "std")]
     #[test]
     fn serde_bincode() {
+        use crate::sdk::wire_bytes::{deserialize, serialize};
+
         // test: `TypedUsize` and `usize` serialize to the same bytes
         let untyped: usize = 12345678;
         let typed = TypedUsize::<TestMarker>::from_usize(untyped);
@@ -93,4 +126,20 @@ mod tests {
         assert_eq!(typed_deserialized, typed);
         assert_eq!(typed_deserialized.as_usize(), untyped);
     }
+
+    #[test]
+    fn conversions() {
+        let typed = TypedUsize::<TestMarker>::from(42);
+        assert_eq!(typed, TypedUsize::from_usize(42));
+        assert_eq!(usize::from(typed), 42);
+        assert_eq!(TypedUsize::<TestMarker>::try_from(42u64).unwrap(), typed);
+
+        // `Display` and `FromStr` round-trip
+        assert_eq!(
+            typed.to_string().parse::<TypedUsize<TestMarker>>(),
+            Ok(typed)
+        );
+        assert!("-1".parse::<TypedUsize<TestMarker>>().is_err());
+        assert!("party 1".parse::<TypedUsize<TestMarker>>().is_err());
+    }
 }
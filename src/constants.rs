@@ -1,6 +1,29 @@
+//! Domain separation tags used to derive deterministic RNGs,
+//! see [rng_seed_signing_key](crate::sdk::api::rng_seed_signing_key).
+
 // Domain separation for protocols/schemes
+
+/// ECDSA over secp256k1, see [crate::ecdsa].
 #[cfg(feature = "secp256k1")]
 pub const ECDSA_TAG: u8 = 0x00;
 
+/// Ed25519, see [crate::ed25519].
 #[cfg(feature = "ed25519")]
 pub const ED25519_TAG: u8 = 0x01;
+
+/// BIP340 Schnorr over secp256k1, see [crate::schnorr].
+#[cfg(feature = "schnorr")]
+pub const SCHNORR_TAG: u8 = 0x02;
+
+/// Sr25519, see [crate::sr25519].
+#[cfg(feature = "sr25519")]
+pub const SR25519_TAG: u8 = 0x03;
+
+/// BLS12-381, see [crate::bls].
+#[cfg(feature = "bls")]
+pub const BLS_TAG: u8 = 0x04;
+
+// Domain separation for the purpose of an RNG within a scheme
+
+/// Signing key generation, common to all schemes.
+pub const KEYGEN_TAG: u8 = 0x00;
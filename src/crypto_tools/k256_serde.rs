@@ -4,19 +4,36 @@
 //!
 //! [Implementing Serialize · Serde](https://serde.rs/impl-serialize.html)
 //! [Implementing Deserialize · Serde](https://serde.rs/impl-deserialize.html)
+//!
+//! ## Secret data
+//!
+//! [Scalar] and [SecretScalar] may hold secret values.
+//! Their decoding and equality checks are constant-time and they impl [ConstantTimeEq].
+//! Neither displays its value in `Debug` output.
+//! [ProjectivePoint] and [Signature] only ever hold public values.
+//!
+//! ## Human-readable formats
+//!
+//! Like [MessageDigest], [Scalar] and [SecretScalar] are encoded as a hex string in human-readable
+//! serde formats (such as JSON) and as raw bytes in binary formats (such as bincode).
+//! The sequence of 32 bytes written by earlier versions of tofn is still accepted.
 
 use crypto_bigint::ArrayEncoding;
 use ecdsa::elliptic_curve::ops::Reduce;
 use ecdsa::elliptic_curve::{
-    consts::U33, generic_array::GenericArray, group::GroupEncoding, Field,
+    consts::U33,
+    generic_array::GenericArray,
+    group::GroupEncoding,
+    subtle::{Choice, ConstantTimeEq},
+    Field, PrimeField,
 };
 use k256::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
 use k256::U256;
 use rand::{CryptoRng, RngCore};
 use serde::{de, de::Error, de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
-use zeroize::Zeroize;
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
-use crate::crypto_tools::message_digest::MessageDigest;
+use crate::crypto_tools::message_digest::{Bytes32Visitor, MessageDigest};
 use crate::sdk::api::BytesVec;
 
 /// Convert a 32-byte hash digest into a scalar as per SEC1:
@@ -37,10 +54,27 @@ impl From<&MessageDigest> for k256::FieldBytes {
 
 /// A wrapper for a random scalar value that is zeroized on drop
 /// TODO why not just do this for Scalar below?
-#[derive(Debug, Serialize, Deserialize, PartialEq, Zeroize)]
-#[zeroize(drop)]
+#[derive(Serialize, Deserialize, Zeroize, ZeroizeOnDrop)]
 pub struct SecretScalar(Scalar);
 
+impl core::fmt::Debug for SecretScalar {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("SecretScalar(<redacted>)")
+    }
+}
+
+impl ConstantTimeEq for SecretScalar {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl PartialEq for SecretScalar {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
 impl AsRef<k256::Scalar> for SecretScalar {
     fn as_ref(&self) -> &k256::Scalar {
         &self.0 .0
@@ -48,6 +82,7 @@ impl AsRef<k256::Scalar> for SecretScalar {
 }
 
 impl SecretScalar {
+    #[cfg(feature = "std")]
     pub fn random_with_thread_rng() -> Self {
         Self(Scalar(k256::Scalar::random(rand::thread_rng())))
     }
@@ -57,9 +92,27 @@ impl SecretScalar {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Zeroize)]
+#[derive(Clone, Zeroize)]
 pub struct Scalar(k256::Scalar);
 
+impl core::fmt::Debug for Scalar {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Scalar(<redacted>)")
+    }
+}
+
+impl ConstantTimeEq for Scalar {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl PartialEq for Scalar {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
 impl AsRef<k256::Scalar> for Scalar {
     fn as_ref(&self) -> &k256::Scalar {
         &self.0
@@ -84,8 +137,17 @@ impl Serialize for Scalar {
     where
         S: Serializer,
     {
-        let bytes: [u8; 32] = self.0.to_bytes().into();
-        bytes.serialize(serializer)
+        let mut bytes: [u8; 32] = self.0.to_bytes().into();
+
+        let result = if serializer.is_human_readable() {
+            serializer.serialize_str(&Zeroizing::new(hex::encode(bytes)))
+        } else {
+            bytes.serialize(serializer)
+        };
+
+        bytes.zeroize();
+
+        result
     }
 }
 
@@ -94,17 +156,21 @@ impl<'de> Deserialize<'de> for Scalar {
     where
         D: Deserializer<'de>,
     {
-        let bytes: [u8; 32] = Deserialize::deserialize(deserializer)?;
-        let field_bytes = k256::FieldBytes::from(bytes);
-        let scalar = k256::Scalar::reduce(U256::from_be_byte_array(bytes.into()));
-
-        // ensure bytes encodes an integer less than the secp256k1 modulus
-        // if not then scalar.to_bytes() will differ from bytes
-        if field_bytes != scalar.to_bytes() {
-            return Err(D::Error::custom("integer exceeds secp256k1 modulus"));
-        }
+        let mut bytes: [u8; 32] = if deserializer.is_human_readable() {
+            deserializer.deserialize_any(Bytes32Visitor)?
+        } else {
+            Deserialize::deserialize(deserializer)?
+        };
+
+        // `from_repr` rejects integers not less than the secp256k1 modulus
+        // without branching on the value of `bytes`
+        let scalar = k256::Scalar::from_repr(bytes.into());
 
-        Ok(Scalar(scalar))
+        bytes.zeroize();
+
+        Option::<k256::Scalar>::from(scalar)
+            .map(Scalar)
+            .ok_or_else(|| D::Error::custom("integer exceeds secp256k1 modulus"))
     }
 }
 
@@ -182,7 +248,7 @@ struct EncodedPointVisitor;
 impl<'de> Visitor<'de> for EncodedPointVisitor {
     type Value = EncodedPoint;
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn expecting(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
         formatter.write_str("SEC1-encoded secp256k1 (K-256) curve point")
     }
 
@@ -277,7 +343,7 @@ fn to_array33(g: GenericArray<u8, U33>) -> [u8; 33] {
     ]
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
     use bincode::Options;
@@ -324,6 +390,74 @@ mod tests {
         assert_eq!(v, v_deserialized);
     }
 
+    #[test]
+    fn scalar_ct_eq() {
+        let s = k256::Scalar::random(rand::thread_rng());
+
+        assert!(bool::from(Scalar(s).ct_eq(&Scalar(s))));
+        assert!(!bool::from(Scalar(s).ct_eq(&Scalar(s + k256::Scalar::ONE))));
+        assert_eq!(SecretScalar(Scalar(s)), SecretScalar(Scalar(s)));
+        assert_ne!(
+            SecretScalar(Scalar(s)),
+            SecretScalar(Scalar(s + k256::Scalar::ONE))
+        );
+    }
+
+    #[test]
+    fn scalar_debug_redacted() {
+        let s = SecretScalar::random(rand::thread_rng());
+        let encoded_scalar = hex::encode(s.as_ref().to_bytes());
+
+        let debug = format!("{:?}", s);
+        assert_eq!(debug, "SecretScalar(<redacted>)");
+        assert_eq!(format!("{:?}", s.0), "Scalar(<redacted>)");
+
+        // key pairs holding a secret scalar do not leak it either
+        let key_pair = crate::ecdsa::keygen(
+            &crate::sdk::key::dummy_secret_recovery_key(42),
+            b"tofn nonce",
+        )
+        .unwrap();
+        let encoded_signing_key = hex::encode(key_pair.signing_key().as_ref().to_bytes());
+
+        for debug in [debug, format!("{:?}", key_pair)] {
+            let debug = debug.to_lowercase();
+            assert!(!debug.contains(&encoded_scalar));
+            assert!(!debug.contains(&encoded_signing_key));
+        }
+    }
+
+    #[test]
+    fn scalar_serde_human_readable() {
+        let s = Scalar(k256::Scalar::from(42u64));
+        let encoded_scalar = hex::encode(s.0.to_bytes());
+
+        let json = serde_json::to_string(&s).unwrap();
+        assert_eq!(json, format!("\"{}\"", encoded_scalar));
+        assert_eq!(serde_json::from_str::<Scalar>(&json).unwrap(), s);
+
+        let secret_scalar = SecretScalar(s.clone());
+        let json = serde_json::to_string(&secret_scalar).unwrap();
+        assert_eq!(json, format!("\"{}\"", encoded_scalar));
+        assert_eq!(
+            serde_json::from_str::<SecretScalar>(&json).unwrap(),
+            secret_scalar
+        );
+
+        // sequence of bytes as written by earlier versions
+        let legacy_json = serde_json::to_string(&s.0.to_bytes().as_slice()).unwrap();
+        assert_eq!(serde_json::from_str::<Scalar>(&legacy_json).unwrap(), s);
+        assert!(
+            serde_json::from_str::<Scalar>(&format!("[{}]", "42,".repeat(31) + "42,42")).is_err()
+        );
+
+        // integers not less than the modulus are rejected in either encoding
+        assert!(serde_json::from_str::<Scalar>(&format!("\"{}\"", "ff".repeat(32))).is_err());
+        assert!(
+            serde_json::from_str::<Scalar>(&serde_json::to_string(&[255u8; 32]).unwrap()).is_err()
+        );
+    }
+
     #[test]
     fn scalar_deserialization_fail() {
         let s = Scalar(k256::Scalar::random(rand::thread_rng()));
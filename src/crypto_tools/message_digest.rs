@@ -1,18 +1,139 @@
-use serde::{Deserialize, Serialize};
-use std::{
-    array::TryFromSliceError,
+use alloc::string::ToString;
+use core::{
     convert::{TryFrom, TryInto},
+    fmt,
 };
+use serde::{
+    de::{Error, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use sha2::{Digest, Sha256, Sha512};
+use sha3::Keccak256;
+use tracing::error;
+
+use crate::sdk::api::{TofnError, TofnResult};
 
 /// Sign only 32-byte hash digests
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// Human-readable serde formats (such as JSON) encode the digest as a hex string,
+/// binary formats (such as bincode) as raw bytes.
+#[derive(Debug, Clone, PartialEq)]
 pub struct MessageDigest(pub(super) [u8; 32]);
 
+/// Hash functions supported by [MessageDigest::hash].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HashScheme<'a> {
+    Sha256,
+    /// Keccak-256 as used by Ethereum (not NIST SHA3-256).
+    Keccak256,
+    /// The first 32 bytes of SHA-512.
+    Sha512Truncated,
+    /// SHA-256 with domain separation as per [BIP340](https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki#design):
+    /// `SHA256(SHA256(tag) || SHA256(tag) || msg)`
+    TaggedSha256(&'a [u8]),
+}
+
+impl MessageDigest {
+    /// Hash an arbitrary-length message into a 32-byte digest using `scheme`.
+    pub fn hash(msg: &[u8], scheme: HashScheme) -> Self {
+        let digest: [u8; 32] = match scheme {
+            HashScheme::Sha256 => Sha256::digest(msg).into(),
+            HashScheme::Keccak256 => Keccak256::digest(msg).into(),
+            HashScheme::Sha512Truncated => {
+                let digest = Sha512::digest(msg);
+                let mut truncated = [0; 32];
+                truncated.copy_from_slice(&digest[..32]);
+                truncated
+            }
+            HashScheme::TaggedSha256(tag) => {
+                let tag_hash = Sha256::digest(tag);
+                Sha256::new()
+                    .chain_update(tag_hash)
+                    .chain_update(tag_hash)
+                    .chain_update(msg)
+                    .finalize()
+                    .into()
+            }
+        };
+
+        Self(digest)
+    }
+
+    /// Construct a digest from `prehash`, the output of the hash function `scheme` computed by the caller.
+    /// Fails if `prehash` is not the length of that hash function's output, or is all zero.
+    /// For [HashScheme::Sha512Truncated] the full 64-byte SHA-512 output is expected.
+    pub fn from_prehash_checked(prehash: &[u8], scheme: HashScheme) -> TofnResult<Self> {
+        let output_len = match scheme {
+            HashScheme::Sha512Truncated => 64,
+            HashScheme::Sha256 | HashScheme::Keccak256 | HashScheme::TaggedSha256(_) => 32,
+        };
+
+        if prehash.len() != output_len {
+            error!(
+                "invalid prehash length {} for {:?}, expected {}",
+                prehash.len(),
+                scheme,
+                output_len
+            );
+            return Err(TofnError::InvalidArgument {
+                reason: "prehash length",
+            });
+        }
+
+        Self::try_from(&prehash[..32])
+    }
+
+    /// Digest signed by Ethereum `personal_sign` as per [EIP-191](https://eips.ethereum.org/EIPS/eip-191):
+    /// `keccak256("\x19Ethereum Signed Message:\n" || len(msg) || msg)` with the length in decimal.
+    pub fn eip191(msg: &[u8]) -> Self {
+        Self(
+            Keccak256::new()
+                .chain_update(b"\x19Ethereum Signed Message:\n")
+                .chain_update(msg.len().to_string())
+                .chain_update(msg)
+                .finalize()
+                .into(),
+        )
+    }
+
+    /// Digest of typed structured data as per [EIP-712](https://eips.ethereum.org/EIPS/eip-712):
+    /// `keccak256("\x19\x01" || domain_separator || hash_struct(message))`
+    pub fn eip712(domain_separator: &[u8; 32], struct_hash: &[u8; 32]) -> Self {
+        Self(
+            Keccak256::new()
+                .chain_update([0x19, 0x01])
+                .chain_update(domain_separator)
+                .chain_update(struct_hash)
+                .finalize()
+                .into(),
+        )
+    }
+}
+
+/// Rejects slices that are not 32 bytes long, and the all-zero digest,
+/// which is never the output of a hash function in practice and maps to the zero scalar.
+///
+/// This is input validation only: `From<[u8; 32]>` and deserialization accept any digest.
+/// ECDSA signing separately rejects every digest that reduces to the zero scalar.
 impl TryFrom<&[u8]> for MessageDigest {
-    type Error = TryFromSliceError;
+    type Error = TofnError;
 
     fn try_from(v: &[u8]) -> Result<Self, Self::Error> {
-        Ok(Self(v.try_into()?))
+        let digest: [u8; 32] = v.try_into().map_err(|_| {
+            error!("invalid message digest length {}, expected 32", v.len());
+            TofnError::InvalidArgument {
+                reason: "message digest length",
+            }
+        })?;
+
+        if digest == [0; 32] {
+            error!("message digest is all zero");
+            return Err(TofnError::InvalidArgument {
+                reason: "zero message digest",
+            });
+        }
+
+        Ok(Self(digest))
     }
 }
 
@@ -27,3 +148,244 @@ impl AsRef<[u8]> for MessageDigest {
         &self.0
     }
 }
+
+impl fmt::Display for MessageDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+    }
+}
+
+impl Serialize for MessageDigest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&hex::encode(self.0))
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MessageDigest {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(Bytes32Visitor).map(Self)
+        } else {
+            Ok(Self(<[u8; 32]>::deserialize(deserializer)?))
+        }
+    }
+}
+
+/// Decodes 32 bytes from human-readable formats, accepting both the hex string
+/// written by [MessageDigest]'s `Serialize` impl and the sequence of 32 bytes
+/// written by earlier versions of tofn.
+pub(super) struct Bytes32Visitor;
+
+impl<'de> Visitor<'de> for Bytes32Visitor {
+    type Value = [u8; 32];
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a hex string or a sequence of 32 bytes")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        let mut digest = [0; 32];
+        hex::decode_to_slice(v, &mut digest).map_err(E::custom)?;
+        Ok(digest)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut digest = [0; 32];
+        for (i, byte) in digest.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| A::Error::invalid_length(i, &self))?;
+        }
+
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(A::Error::invalid_length(digest.len() + 1, &self));
+        }
+
+        Ok(digest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HashScheme, MessageDigest};
+    use sha2::{Digest, Sha256, Sha512};
+
+    #[test]
+    fn hash_known_vectors() {
+        let test_cases = [
+            (
+                HashScheme::Sha256,
+                &b"abc"[..],
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            ),
+            (
+                HashScheme::Keccak256,
+                &b""[..],
+                "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+            ),
+            (
+                HashScheme::Sha512Truncated,
+                &b"abc"[..],
+                "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a",
+            ),
+        ];
+
+        for (scheme, msg, expected) in test_cases {
+            assert_eq!(
+                hex::encode(MessageDigest::hash(msg, scheme)),
+                expected,
+                "{:?}",
+                scheme
+            );
+        }
+    }
+
+    #[test]
+    fn eip191_known_vector() {
+        assert_eq!(
+            hex::encode(MessageDigest::eip191(b"hello world")),
+            "d9eba16ed0ecae432b71fe008c98cc872bb4cc214d3220a36f365326cf807d68"
+        );
+    }
+
+    /// `Mail` example from <https://github.com/ethereum/EIPs/blob/master/assets/eip-712/Example.js>
+    #[test]
+    fn eip712_known_vector() {
+        let domain_separator: [u8; 32] =
+            hex::decode("f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let struct_hash: [u8; 32] =
+            hex::decode("c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e")
+                .unwrap()
+                .try_into()
+                .unwrap();
+
+        assert_eq!(
+            hex::encode(MessageDigest::eip712(&domain_separator, &struct_hash)),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+    }
+
+    #[test]
+    fn hash_tagged() {
+        let tag = b"tofn/test";
+        let msg = b"hello";
+
+        let tag_hash = Sha256::digest(tag);
+        let expected: [u8; 32] =
+            Sha256::digest([&tag_hash[..], &tag_hash[..], &msg[..]].concat()).into();
+
+        assert_eq!(
+            MessageDigest::hash(msg, HashScheme::TaggedSha256(tag)),
+            expected.into()
+        );
+
+        // different tags yield different digests
+        assert_ne!(
+            MessageDigest::hash(msg, HashScheme::TaggedSha256(b"tofn/other")),
+            expected.into()
+        );
+    }
+
+    #[test]
+    fn serde_human_readable() {
+        let message_digest = MessageDigest::from([42; 32]);
+
+        let json = serde_json::to_string(&message_digest).unwrap();
+        assert_eq!(json, format!("\"{}\"", "2a".repeat(32)));
+        assert_eq!(
+            serde_json::from_str::<MessageDigest>(&json).unwrap(),
+            message_digest
+        );
+
+        // wrong length or non-hex input
+        assert!(
+            serde_json::from_str::<MessageDigest>(&format!("\"{}\"", "2a".repeat(31))).is_err()
+        );
+        assert!(
+            serde_json::from_str::<MessageDigest>(&format!("\"{}\"", "zz".repeat(32))).is_err()
+        );
+
+        // sequence of bytes as written by earlier versions
+        let legacy_json = serde_json::to_string(&[42u8; 32]).unwrap();
+        assert_eq!(
+            serde_json::from_str::<MessageDigest>(&legacy_json).unwrap(),
+            message_digest
+        );
+        assert!(serde_json::from_str::<MessageDigest>(
+            &serde_json::to_string(&vec![42u8; 31]).unwrap()
+        )
+        .is_err());
+        assert!(serde_json::from_str::<MessageDigest>(
+            &serde_json::to_string(&vec![42u8; 33]).unwrap()
+        )
+        .is_err());
+        assert!(
+            serde_json::from_str::<MessageDigest>(&format!("[256{}]", ",42".repeat(31))).is_err()
+        );
+
+        // binary formats are unaffected
+        #[cfg(feature = "std")]
+        assert_eq!(
+            crate::sdk::wire_bytes::serialize(&message_digest).unwrap(),
+            [42; 32]
+        );
+    }
+
+    #[test]
+    fn checked_constructors() {
+        let digest = Sha256::digest(b"abc");
+        assert_eq!(
+            MessageDigest::try_from(&digest[..]).unwrap(),
+            MessageDigest::hash(b"abc", HashScheme::Sha256)
+        );
+        assert_eq!(
+            MessageDigest::from_prehash_checked(&digest, HashScheme::Sha256).unwrap(),
+            MessageDigest::hash(b"abc", HashScheme::Sha256)
+        );
+
+        // SHA-512 prehashes must be the full 64-byte output
+        let digest = Sha512::digest(b"abc");
+        assert_eq!(
+            MessageDigest::from_prehash_checked(&digest, HashScheme::Sha512Truncated).unwrap(),
+            MessageDigest::hash(b"abc", HashScheme::Sha512Truncated)
+        );
+        assert!(
+            MessageDigest::from_prehash_checked(&digest[..32], HashScheme::Sha512Truncated)
+                .is_err()
+        );
+        assert!(MessageDigest::from_prehash_checked(&digest, HashScheme::Keccak256).is_err());
+
+        // zero digest and wrong lengths are rejected
+        assert!(MessageDigest::try_from(&[0; 32][..]).is_err());
+        assert!(MessageDigest::from_prehash_checked(&[0; 32], HashScheme::Sha256).is_err());
+        assert!(MessageDigest::try_from(&[1; 31][..]).is_err());
+        assert!(MessageDigest::try_from(&[1; 33][..]).is_err());
+    }
+
+    #[test]
+    fn display_hex() {
+        assert_eq!(
+            MessageDigest::hash(b"abc", HashScheme::Sha256).to_string(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
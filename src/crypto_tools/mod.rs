@@ -3,5 +3,10 @@ pub mod k256_serde;
 
 pub mod message_digest;
 
-#[cfg(any(feature = "secp256k1", feature = "ed25519"))]
+#[cfg(any(
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "sr25519",
+    feature = "bls"
+))]
 pub mod rng;
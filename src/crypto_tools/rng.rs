@@ -3,19 +3,28 @@ use rand::{CryptoRng, RngCore, SeedableRng};
 use rand_chacha::ChaCha20Rng;
 use sha2::Sha256;
 use tracing::error;
-use zeroize::Zeroize;
+use zeroize::{Zeroize, Zeroizing};
 
-use crate::sdk::{
-    api::{TofnFatal, TofnResult},
-    key::SecretRecoveryKey,
+#[cfg(any(feature = "schnorr", feature = "sr25519"))]
+use crate::crypto_tools::message_digest::MessageDigest;
+use crate::{
+    constants::KEYGEN_TAG,
+    sdk::{
+        api::{TofnError, TofnResult},
+        key::SecretRecoveryKey,
+    },
 };
 
 const SESSION_NONCE_LENGTH_MIN: usize = 4;
 const SESSION_NONCE_LENGTH_MAX: usize = 256;
 
 /// Initialize a RNG by hashing the arguments.
-/// Intended for use generating a ECDSA signing key.
-pub(crate) fn rng_seed_signing_key(
+/// Intended for use generating a signing key.
+///
+/// Returns `ChaCha20Rng::from_seed(HMAC-SHA256(secret_recovery_key, protocol_tag || tag || session_nonce))`,
+/// where the tags are single bytes and `session_nonce` must be 4 to 256 bytes long.
+/// Keygen for each scheme uses its domain tag from [crate::constants] as `protocol_tag` and [KEYGEN_TAG] as `tag`.
+pub fn rng_seed_signing_key(
     protocol_tag: u8,
     tag: u8,
     secret_recovery_key: &SecretRecoveryKey,
@@ -30,22 +39,46 @@ pub(crate) fn rng_seed_signing_key(
             SESSION_NONCE_LENGTH_MIN,
             SESSION_NONCE_LENGTH_MAX
         );
-        return Err(TofnFatal);
+        return Err(TofnError::InvalidArgument {
+            reason: "session nonce length",
+        });
     }
 
     let mut prf =
         SimpleHmac::<Sha256>::new_from_slice(&secret_recovery_key.0[..]).map_err(|_| {
             error!("failure to initialize hmac");
-            TofnFatal
+            TofnError::RngFailure
         })?;
 
     prf.update(&protocol_tag.to_be_bytes());
     prf.update(&tag.to_be_bytes());
     prf.update(session_nonce);
 
-    let seed = prf.finalize().into_bytes().into();
+    let mut seed: [u8; 32] = prf.finalize().into_bytes().into();
+    let rng = ChaCha20Rng::from_seed(seed);
 
-    Ok(ChaCha20Rng::from_seed(seed))
+    seed.zeroize();
+
+    Ok(rng)
+}
+
+/// Returns the first 32 bytes drawn by keygen for the scheme with domain tag `scheme_tag`.
+/// These are the Ed25519 secret key, the sr25519 mini secret key and the BLS key material.
+/// For secp256k1 schemes they are the big-endian secret scalar before any normalization,
+/// unless they exceed the group order (probability about 2^-128), in which case keygen draws again.
+/// Schnorr (BIP340) keygen negates this scalar if its public key has an odd Y coordinate,
+/// so the signing key it holds may differ from these bytes while the x-only public key matches.
+pub fn derive_signing_key(
+    scheme_tag: u8,
+    secret_recovery_key: &SecretRecoveryKey,
+    session_nonce: &[u8],
+) -> TofnResult<Zeroizing<[u8; 32]>> {
+    let mut rng = rng_seed_signing_key(scheme_tag, KEYGEN_TAG, secret_recovery_key, session_nonce)?;
+
+    let mut signing_key = Zeroizing::new([0; 32]);
+    rng.fill_bytes(signing_key.as_mut());
+
+    Ok(signing_key)
 }
 
 /// Initialize a RNG by hashing the arguments.
@@ -71,9 +104,40 @@ pub(crate) fn rng_seed_ecdsa_ephemeral_scalar(
 
     signing_key_bytes.zeroize();
 
-    let seed = prf.finalize().into_bytes().into();
+    let mut seed: [u8; 32] = prf.finalize().into_bytes().into();
+    let rng = ChaCha20Rng::from_seed(seed);
 
-    Ok(ChaCha20Rng::from_seed(seed))
+    seed.zeroize();
+
+    Ok(rng)
+}
+
+/// Initialize a RNG by hashing the arguments.
+/// Intended for use as per-signature randomness (such as an auxiliary nonce input or witness)
+/// for any scheme that takes its signing key as bytes.
+/// Deriving it deterministically keeps signing reproducible.
+#[cfg(any(feature = "schnorr", feature = "sr25519"))]
+pub(crate) fn rng_seed_signature(
+    protocol_tag: u8,
+    tag: u8,
+    secret_key_bytes: &[u8],
+    message_digest: &MessageDigest,
+) -> TofnResult<impl CryptoRng + RngCore> {
+    let mut prf = SimpleHmac::<Sha256>::new_from_slice(secret_key_bytes).map_err(|_| {
+        error!("failure to initialize hmac");
+        TofnError::RngFailure
+    })?;
+
+    prf.update(&protocol_tag.to_be_bytes());
+    prf.update(&tag.to_be_bytes());
+    prf.update(message_digest.as_ref());
+
+    let mut seed: [u8; 32] = prf.finalize().into_bytes().into();
+    let rng = ChaCha20Rng::from_seed(seed);
+
+    seed.zeroize();
+
+    Ok(rng)
 }
 
 #[cfg(test)]
@@ -193,4 +257,84 @@ mod tests {
 
         goldie::assert_json!(expected_outputs);
     }
+
+    /// External tools can reproduce each scheme's keygen from [derive_signing_key].
+    #[test]
+    fn derive_signing_key_matches_keygen() {
+        use crate::{constants::*, crypto_tools::rng::derive_signing_key};
+        use k256::elliptic_curve::sec1::ToEncodedPoint;
+
+        let secret_recovery_key = SecretRecoveryKey([0x42; 64]);
+        let session_nonce = b"tofn nonce";
+
+        let signing_key =
+            derive_signing_key(ECDSA_TAG, &secret_recovery_key, session_nonce).unwrap();
+        let key_pair = crate::ecdsa::keygen(&secret_recovery_key, session_nonce).unwrap();
+        assert_eq!(
+            k256::SecretKey::from_slice(signing_key.as_ref())
+                .unwrap()
+                .public_key()
+                .to_encoded_point(true)
+                .as_bytes(),
+            key_pair.encoded_verifying_key()
+        );
+
+        #[cfg(feature = "ed25519")]
+        {
+            let signing_key =
+                derive_signing_key(ED25519_TAG, &secret_recovery_key, session_nonce).unwrap();
+            let key_pair = crate::ed25519::keygen(&secret_recovery_key, session_nonce).unwrap();
+            assert_eq!(
+                ed25519_dalek::SigningKey::from_bytes(&signing_key)
+                    .verifying_key()
+                    .to_bytes(),
+                key_pair.encoded_verifying_key()
+            );
+        }
+
+        #[cfg(feature = "schnorr")]
+        {
+            let signing_key =
+                derive_signing_key(SCHNORR_TAG, &secret_recovery_key, session_nonce).unwrap();
+            let key_pair = crate::schnorr::keygen(&secret_recovery_key, session_nonce).unwrap();
+            // keygen may negate the secret scalar, so only the x-only public keys are compared
+            assert_eq!(
+                k256::schnorr::SigningKey::from_bytes(signing_key.as_ref())
+                    .unwrap()
+                    .verifying_key()
+                    .to_bytes(),
+                key_pair.encoded_verifying_key().into()
+            );
+        }
+
+        #[cfg(feature = "sr25519")]
+        {
+            use schnorrkel::{ExpansionMode, MiniSecretKey};
+
+            let signing_key =
+                derive_signing_key(SR25519_TAG, &secret_recovery_key, session_nonce).unwrap();
+            let key_pair = crate::sr25519::keygen(&secret_recovery_key, session_nonce).unwrap();
+            assert_eq!(
+                MiniSecretKey::from_bytes(signing_key.as_ref())
+                    .unwrap()
+                    .expand_to_public(ExpansionMode::Ed25519)
+                    .to_bytes(),
+                key_pair.encoded_verifying_key()
+            );
+        }
+
+        #[cfg(feature = "bls")]
+        {
+            let signing_key =
+                derive_signing_key(BLS_TAG, &secret_recovery_key, session_nonce).unwrap();
+            let key_pair = crate::bls::keygen(&secret_recovery_key, session_nonce).unwrap();
+            assert_eq!(
+                blst::min_pk::SecretKey::key_gen(signing_key.as_ref(), &[])
+                    .unwrap()
+                    .sk_to_pk()
+                    .to_bytes(),
+                key_pair.encoded_verifying_key()
+            );
+        }
+    }
 }
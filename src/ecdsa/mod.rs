@@ -1,24 +1,28 @@
-use std::convert::TryInto;
+use core::convert::TryInto;
 
 use ecdsa::{
     elliptic_curve::{sec1::ToEncodedPoint, Field},
     hazmat::{SignPrimitive, VerifyPrimitive},
+    RecoveryId,
 };
 use message_digest::MessageDigest;
+use sha2::Sha256;
 use tracing::error;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use crate::{
-    constants::ECDSA_TAG,
+    constants::{ECDSA_TAG, KEYGEN_TAG},
     crypto_tools::{k256_serde, message_digest, rng},
     sdk::{
-        api::{BytesVec, TofnFatal, TofnResult},
+        api::{BytesVec, TofnError, TofnResult},
         key::SecretRecoveryKey,
     },
 };
 
-#[derive(Debug)]
+#[derive(Debug, ZeroizeOnDrop)]
 pub struct KeyPair {
     signing_key: k256_serde::SecretScalar,
+    #[zeroize(skip)]
     encoded_verifying_key: [u8; 33], // SEC1-encoded compressed curve point
 }
 
@@ -52,7 +56,9 @@ pub fn keygen(
         .try_into()
         .map_err(|_| {
             error!("failure to convert ecdsa verifying key to 33-byte array");
-            TofnFatal
+            TofnError::Internal {
+                reason: "failure to encode verifying key",
+            }
         })?;
 
     Ok(KeyPair {
@@ -67,28 +73,138 @@ pub fn sign(
     signing_key: &k256_serde::SecretScalar,
     message_digest: &MessageDigest,
 ) -> TofnResult<BytesVec> {
-    let signing_key = signing_key.as_ref();
+    let (signature, _) = sign_prehashed(signing_key, message_digest)?;
+
+    Ok(k256_serde::Signature::from(signature).to_bytes())
+}
+
+/// Returns a ASN.1 DER-encoded ECDSA signature
+/// whose ephemeral scalar is derived as per [RFC 6979](https://datatracker.ietf.org/doc/html/rfc6979) with SHA-256.
+/// Unlike [sign], the output is reproducible by any standard RFC 6979 implementation.
+pub fn sign_rfc6979(
+    signing_key: &k256_serde::SecretScalar,
+    message_digest: &MessageDigest,
+) -> TofnResult<BytesVec> {
+    let message_digest_scalar = message_digest_scalar(message_digest)?;
+
+    let (signature, _) = signing_key
+        .as_ref()
+        .try_sign_prehashed_rfc6979::<Sha256>(&message_digest_scalar.to_bytes(), &[])
+        .map_err(|_| {
+            error!("failure to sign");
+            TofnError::Internal {
+                reason: "failure to sign",
+            }
+        })?;
+
+    Ok(k256_serde::Signature::from(signature).to_bytes())
+}
+
+/// Returns a recoverable ECDSA signature encoded as `[r || s || v]`,
+/// where `s` is normalized to the lower half of the curve order
+/// and `v` is the recovery id in `0..=3`.
+/// Ethereum's `ecrecover` expects `v + 27`.
+pub fn sign_recoverable(
+    signing_key: &k256_serde::SecretScalar,
+    message_digest: &MessageDigest,
+) -> TofnResult<[u8; 65]> {
+    let (signature, recovery_id) = sign_prehashed(signing_key, message_digest)?;
+
+    let mut encoded_signature = [0; 65];
+    encoded_signature[..64].copy_from_slice(&signature.to_bytes());
+    encoded_signature[64] = recovery_id.to_byte();
+
+    Ok(encoded_signature)
+}
+
+/// Recover the SEC1-encoded compressed verifying key from a signature produced by [sign_recoverable].
+pub fn recover_verifying_key(
+    message_digest: &MessageDigest,
+    encoded_signature: &[u8; 65],
+) -> TofnResult<[u8; 33]> {
+    let signature = k256::ecdsa::Signature::from_slice(&encoded_signature[..64]).map_err(|_| {
+        TofnError::InvalidEncoding {
+            kind: "ecdsa signature",
+        }
+    })?;
+    let recovery_id =
+        RecoveryId::from_byte(encoded_signature[64]).ok_or(TofnError::InvalidEncoding {
+            kind: "ecdsa recovery id",
+        })?;
+
+    let verifying_key = k256::ecdsa::VerifyingKey::recover_from_prehash(
+        &k256::FieldBytes::from(message_digest),
+        &signature,
+        recovery_id,
+    )
+    .map_err(|_| TofnError::InvalidArgument {
+        reason: "no verifying key recoverable from signature",
+    })?;
+
+    verifying_key
+        .to_encoded_point(true)
+        .as_bytes()
+        .try_into()
+        .map_err(|_| {
+            error!("failure to convert ecdsa verifying key to 33-byte array");
+            TofnError::Internal {
+                reason: "failure to encode verifying key",
+            }
+        })
+}
+
+/// A message digest that reduces to the zero scalar lets anyone forge signatures on it without the signing key,
+/// so it is rejected however the [MessageDigest] was constructed.
+fn message_digest_scalar(message_digest: &MessageDigest) -> TofnResult<k256::Scalar> {
     let message_digest_scalar = k256::Scalar::from(message_digest);
 
+    if bool::from(message_digest_scalar.is_zero()) {
+        error!("message digest reduces to the zero scalar");
+        return Err(TofnError::InvalidArgument {
+            reason: "zero message digest",
+        });
+    }
+
+    Ok(message_digest_scalar)
+}
+
+/// Sign with a deterministic ephemeral scalar.
+/// The returned signature has low-s normalization applied.
+fn sign_prehashed(
+    signing_key: &k256_serde::SecretScalar,
+    message_digest: &MessageDigest,
+) -> TofnResult<(k256::ecdsa::Signature, RecoveryId)> {
+    let signing_key = signing_key.as_ref();
+    let message_digest_scalar = message_digest_scalar(message_digest)?;
+
     let rng = rng::rng_seed_ecdsa_ephemeral_scalar(
         ECDSA_TAG,
         SIGN_TAG,
         signing_key,
         &message_digest_scalar,
     )?;
-    let ephemeral_scalar = k256::Scalar::random(rng);
-
-    let signature = k256_serde::Signature::from(
-        signing_key
-            .try_sign_prehashed(ephemeral_scalar, &message_digest_scalar.to_bytes())
-            .map_err(|_| {
-                error!("failure to sign");
-                TofnFatal
-            })
-            .map(|(r, _)| r)?,
-    );
+    let mut ephemeral_scalar = k256::Scalar::random(rng);
+
+    let result =
+        signing_key.try_sign_prehashed(ephemeral_scalar, &message_digest_scalar.to_bytes());
 
-    Ok(signature.to_bytes())
+    ephemeral_scalar.zeroize();
+
+    let (signature, recovery_id) = result.map_err(|_| {
+        error!("failure to sign");
+        TofnError::Internal {
+            reason: "failure to sign",
+        }
+    })?;
+
+    let recovery_id = recovery_id.ok_or_else(|| {
+        error!("missing recovery id");
+        TofnError::Internal {
+            reason: "missing recovery id",
+        }
+    })?;
+
+    Ok((signature, recovery_id))
 }
 
 pub fn verify(
@@ -96,9 +212,16 @@ pub fn verify(
     message_digest: &MessageDigest,
     encoded_signature: &[u8],
 ) -> TofnResult<bool> {
-    let verifying_key =
-        k256_serde::ProjectivePoint::from_bytes(encoded_verifying_key).ok_or(TofnFatal)?;
-    let signature = k256::ecdsa::Signature::from_der(encoded_signature).map_err(|_| TofnFatal)?;
+    let verifying_key = k256_serde::ProjectivePoint::from_bytes(encoded_verifying_key).ok_or(
+        TofnError::InvalidEncoding {
+            kind: "ecdsa verifying key",
+        },
+    )?;
+    let signature = k256::ecdsa::Signature::from_der(encoded_signature).map_err(|_| {
+        TofnError::InvalidEncoding {
+            kind: "ecdsa signature",
+        }
+    })?;
 
     Ok(verifying_key
         .as_ref()
@@ -108,13 +231,16 @@ pub fn verify(
 }
 
 /// Domain separation for seeding the RNG
-const KEYGEN_TAG: u8 = 0x00;
 const SIGN_TAG: u8 = 0x01;
 
 #[cfg(test)]
 mod tests {
-    use super::{keygen, sign, verify};
-    use crate::sdk::key::{dummy_secret_recovery_key, SecretRecoveryKey};
+    use super::{keygen, recover_verifying_key, sign, sign_recoverable, sign_rfc6979, verify};
+    use crate::sdk::{
+        api::{MessageDigest, TofnError},
+        key::{dummy_secret_recovery_key, SecretRecoveryKey},
+    };
+    use ecdsa::signature::hazmat::PrehashSigner;
 
     #[test]
     fn keygen_sign_decode_verify() {
@@ -132,6 +258,113 @@ mod tests {
         assert!(success);
     }
 
+    #[test]
+    fn sign_zero_digest() {
+        let key_pair = keygen(&dummy_secret_recovery_key(42), b"tofn nonce").unwrap();
+
+        // the secp256k1 group order also reduces to zero
+        let group_order: [u8; 32] =
+            hex::decode("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141")
+                .unwrap()
+                .try_into()
+                .unwrap();
+
+        for message_digest in [[0; 32].into(), group_order.into()] {
+            let expected = TofnError::InvalidArgument {
+                reason: "zero message digest",
+            };
+
+            assert_eq!(
+                sign(key_pair.signing_key(), &message_digest).unwrap_err(),
+                expected
+            );
+            assert_eq!(
+                sign_rfc6979(key_pair.signing_key(), &message_digest).unwrap_err(),
+                expected
+            );
+            assert_eq!(
+                sign_recoverable(key_pair.signing_key(), &message_digest).unwrap_err(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn sign_recoverable_recover() {
+        let message_digest = [42; 32].into();
+
+        let key_pair = keygen(&dummy_secret_recovery_key(42), b"tofn nonce").unwrap();
+        let encoded_signature = sign_recoverable(key_pair.signing_key(), &message_digest).unwrap();
+
+        // same (r, s) as the DER-encoded signature
+        let der_signature = k256::ecdsa::Signature::from_der(
+            &sign(key_pair.signing_key(), &message_digest).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            &encoded_signature[..64],
+            der_signature.to_bytes().as_slice()
+        );
+        assert!(der_signature.normalize_s().is_none());
+
+        let recovered_key = recover_verifying_key(&message_digest, &encoded_signature).unwrap();
+        assert_eq!(&recovered_key, key_pair.encoded_verifying_key());
+
+        // wrong digest recovers a different key
+        let recovered_key = recover_verifying_key(&[43; 32].into(), &encoded_signature).unwrap();
+        assert_ne!(&recovered_key, key_pair.encoded_verifying_key());
+    }
+
+    /// Test vector from <https://github.com/bitcoinjs/bitcoinjs-lib/blob/master/test/fixtures/ecdsa.json>
+    #[cfg(feature = "std")]
+    #[test]
+    fn sign_rfc6979_known_vector() {
+        use crate::{crypto_tools::k256_serde, sdk::api::HashScheme};
+
+        let mut signing_key_bytes = [0; 32];
+        signing_key_bytes[31] = 1;
+        let signing_key: k256_serde::SecretScalar =
+            crate::sdk::wire_bytes::deserialize(&signing_key_bytes).unwrap();
+        let message_digest = MessageDigest::hash(b"Satoshi Nakamoto", HashScheme::Sha256);
+
+        let encoded_signature = sign_rfc6979(&signing_key, &message_digest).unwrap();
+        let signature = k256::ecdsa::Signature::from_der(&encoded_signature).unwrap();
+
+        assert_eq!(
+            hex::encode(signature.to_bytes()),
+            "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d82442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5"
+        );
+    }
+
+    /// Cross-check against the RFC 6979 signer from RustCrypto.
+    #[test]
+    fn sign_rfc6979_matches_rustcrypto() {
+        for i in 1..=8 {
+            let message_digest = MessageDigest::from([i; 32]);
+            let key_pair = keygen(&dummy_secret_recovery_key(i.into()), b"tofn nonce").unwrap();
+
+            let encoded_signature = sign_rfc6979(key_pair.signing_key(), &message_digest).unwrap();
+
+            let rustcrypto_signing_key =
+                k256::ecdsa::SigningKey::from_bytes(&key_pair.signing_key().as_ref().to_bytes())
+                    .unwrap();
+            let expected_signature: k256::ecdsa::Signature = rustcrypto_signing_key
+                .sign_prehash(message_digest.as_ref())
+                .unwrap();
+
+            assert_eq!(
+                encoded_signature,
+                expected_signature.to_der().as_bytes().to_vec()
+            );
+            assert!(verify(
+                key_pair.encoded_verifying_key(),
+                &message_digest,
+                &encoded_signature
+            )
+            .unwrap());
+        }
+    }
+
     /// Check keygen/signing outputs against golden files to catch regressions (such as on updating deps).
     /// Golden files were generated from tofn commit corresponding to tofnd v0.10.1 release
     #[test]
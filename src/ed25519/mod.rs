@@ -1,14 +1,15 @@
 use crate::{
-    constants::ED25519_TAG,
+    constants::{ED25519_TAG, KEYGEN_TAG},
     crypto_tools::{message_digest::MessageDigest, rng},
     sdk::{
-        api::{BytesVec, TofnFatal, TofnResult},
+        api::{BytesVec, TofnError, TofnResult},
         key::SecretRecoveryKey,
     },
 };
 use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey, PUBLIC_KEY_LENGTH};
+use zeroize::ZeroizeOnDrop;
 
-#[derive(Debug)]
+#[derive(Debug, ZeroizeOnDrop)]
 pub struct KeyPair(SigningKey);
 
 impl KeyPair {
@@ -44,18 +45,22 @@ pub fn verify(
     message_digest: &MessageDigest,
     encoded_signature: &[u8],
 ) -> TofnResult<bool> {
-    let verifying_key = VerifyingKey::from_bytes(encoded_verifying_key).map_err(|_| TofnFatal)?;
+    let verifying_key = VerifyingKey::from_bytes(encoded_verifying_key).map_err(|_| {
+        TofnError::InvalidEncoding {
+            kind: "ed25519 verifying key",
+        }
+    })?;
 
-    let signature = Signature::from_slice(encoded_signature).map_err(|_| TofnFatal)?;
+    let signature =
+        Signature::from_slice(encoded_signature).map_err(|_| TofnError::InvalidEncoding {
+            kind: "ed25519 signature",
+        })?;
 
     Ok(verifying_key
         .verify_strict(message_digest.as_ref(), &signature)
         .is_ok())
 }
 
-/// Domain separation for seeding the RNG
-const KEYGEN_TAG: u8 = 0x00;
-
 #[cfg(test)]
 mod tests {
     use super::{keygen, sign, verify};
@@ -1,6 +1,10 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 pub mod collections;
 
-mod constants;
+pub mod constants;
 
 pub mod sdk;
 
@@ -11,3 +15,30 @@ pub mod ecdsa;
 
 #[cfg(feature = "ed25519")]
 pub mod ed25519;
+
+#[cfg(all(feature = "secp256k1", feature = "std"))]
+pub mod multisig;
+
+#[cfg(feature = "schnorr")]
+pub mod schnorr;
+
+#[cfg(feature = "sr25519")]
+pub mod sr25519;
+
+#[cfg(feature = "bls")]
+pub mod bls;
+
+#[cfg(any(
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "sr25519",
+    feature = "bls"
+))]
+mod scheme;
+#[cfg(any(
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "sr25519",
+    feature = "bls"
+))]
+pub use scheme::{keygen, sign, verify, KeyPair, SignatureScheme};
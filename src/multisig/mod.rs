@@ -0,0 +1,317 @@
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::{
+    collections::TypedUsize,
+    crypto_tools::{k256_serde, message_digest::MessageDigest},
+    ecdsa,
+    sdk::{
+        api::{BytesVec, TofnError, TofnResult},
+        wire_bytes,
+    },
+};
+
+/// Marker for indices into the list of registered verifying keys of a [MultisigGroup].
+pub struct MultisigSignerId;
+pub type SignerIndex = TypedUsize<MultisigSignerId>;
+
+/// Collects ECDSA signatures on a single message digest from a fixed, ordered set of signers.
+#[derive(Debug)]
+pub struct MultisigGroup {
+    encoded_verifying_keys: Vec<[u8; 33]>, // SEC1-encoded compressed curve points
+    message_digest: MessageDigest,
+    signatures: Vec<Option<k256_serde::Signature>>,
+}
+
+/// Canonical encoding of a set of signatures ordered by strictly increasing signer index.
+#[derive(Debug, Serialize, Deserialize)]
+struct AggregateSignature {
+    signatures: Vec<(SignerIndex, k256_serde::Signature)>,
+}
+
+impl MultisigGroup {
+    /// Register the SEC1-encoded verifying keys of all signers.
+    /// The position of a key in `encoded_verifying_keys` is that signer's [SignerIndex].
+    pub fn new(
+        encoded_verifying_keys: Vec<[u8; 33]>,
+        message_digest: MessageDigest,
+    ) -> TofnResult<Self> {
+        if encoded_verifying_keys.is_empty() {
+            error!("multisig group must have at least one signer");
+            return Err(TofnError::InvalidArgument {
+                reason: "empty signer list",
+            });
+        }
+
+        for (i, key) in encoded_verifying_keys.iter().enumerate() {
+            if k256_serde::ProjectivePoint::from_bytes(key).is_none() {
+                error!("invalid verifying key for signer {}", i);
+                return Err(TofnError::InvalidEncoding {
+                    kind: "verifying key",
+                });
+            }
+
+            if encoded_verifying_keys[..i].contains(key) {
+                error!("duplicate verifying key for signer {}", i);
+                return Err(TofnError::InvalidArgument {
+                    reason: "duplicate verifying key",
+                });
+            }
+        }
+
+        let signatures = vec![None; encoded_verifying_keys.len()];
+
+        Ok(Self {
+            encoded_verifying_keys,
+            message_digest,
+            signatures,
+        })
+    }
+
+    pub fn encoded_verifying_keys(&self) -> &[[u8; 33]] {
+        &self.encoded_verifying_keys
+    }
+
+    pub fn message_digest(&self) -> &MessageDigest {
+        &self.message_digest
+    }
+
+    /// Number of valid signatures collected so far.
+    pub fn signature_count(&self) -> usize {
+        self.signatures.iter().filter(|s| s.is_some()).count()
+    }
+
+    /// Verify a ASN.1 DER-encoded ECDSA signature from `signer` and store it.
+    /// Fails if `signer` is out of range, has already signed, or the signature does not verify.
+    pub fn add_signature(
+        &mut self,
+        signer: SignerIndex,
+        encoded_signature: &[u8],
+    ) -> TofnResult<()> {
+        let encoded_verifying_key = self
+            .encoded_verifying_keys
+            .get(signer.as_usize())
+            .ok_or_else(|| {
+                error!("signer index {} out of range", signer);
+                TofnError::BadSignerIndex {
+                    index: signer.as_usize(),
+                }
+            })?;
+
+        if self.signatures[signer.as_usize()].is_some() {
+            error!("duplicate signature from signer {}", signer);
+            return Err(TofnError::InvalidArgument {
+                reason: "signer already signed",
+            });
+        }
+
+        if !ecdsa::verify(
+            encoded_verifying_key,
+            &self.message_digest,
+            encoded_signature,
+        )? {
+            error!("invalid signature from signer {}", signer);
+            return Err(TofnError::InvalidSignature {
+                index: signer.as_usize(),
+            });
+        }
+
+        let signature = k256_serde::Signature::from_bytes(encoded_signature).ok_or(
+            TofnError::InvalidEncoding {
+                kind: "ecdsa signature",
+            },
+        )?;
+        self.signatures[signer.as_usize()] = Some(signature);
+
+        Ok(())
+    }
+
+    /// Returns the collected signatures encoded canonically in order of signer index.
+    pub fn aggregate(&self) -> TofnResult<BytesVec> {
+        let signatures = self
+            .signatures
+            .iter()
+            .enumerate()
+            .filter_map(|(i, s)| Some((SignerIndex::from_usize(i), s.clone()?)))
+            .collect();
+
+        wire_bytes::serialize(&AggregateSignature { signatures })
+    }
+}
+
+/// Returns `true` if `encoded_aggregate` (as produced by [MultisigGroup::aggregate])
+/// contains valid signatures on `message_digest` from at least `quorum` distinct signers
+/// in `encoded_verifying_keys`.
+/// A `quorum` of zero is rejected, since any aggregate would satisfy it.
+/// Duplicate keys in `encoded_verifying_keys` are rejected, since one signer could then
+/// be counted more than once.
+pub fn verify_quorum(
+    encoded_verifying_keys: &[[u8; 33]],
+    quorum: usize,
+    message_digest: &MessageDigest,
+    encoded_aggregate: &[u8],
+) -> TofnResult<bool> {
+    if quorum == 0 {
+        error!("multisig quorum must be at least one");
+        return Err(TofnError::InvalidArgument {
+            reason: "zero quorum",
+        });
+    }
+
+    for (i, key) in encoded_verifying_keys.iter().enumerate() {
+        if encoded_verifying_keys[..i].contains(key) {
+            error!("duplicate verifying key for signer {}", i);
+            return Err(TofnError::InvalidArgument {
+                reason: "duplicate verifying key",
+            });
+        }
+    }
+
+    let aggregate: AggregateSignature =
+        wire_bytes::deserialize(encoded_aggregate).ok_or(TofnError::InvalidEncoding {
+            kind: "aggregate signature",
+        })?;
+
+    // signer indices must be strictly increasing, which also rules out duplicate signers
+    if aggregate
+        .signatures
+        .windows(2)
+        .any(|w| w[0].0.as_usize() >= w[1].0.as_usize())
+    {
+        return Ok(false);
+    }
+
+    if aggregate.signatures.len() < quorum {
+        return Ok(false);
+    }
+
+    for (signer, signature) in aggregate.signatures.iter() {
+        let Some(encoded_verifying_key) = encoded_verifying_keys.get(signer.as_usize()) else {
+            return Ok(false);
+        };
+
+        if !ecdsa::verify(encoded_verifying_key, message_digest, &signature.to_bytes())? {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{verify_quorum, AggregateSignature, MultisigGroup, SignerIndex};
+    use crate::{
+        crypto_tools::k256_serde,
+        ecdsa,
+        sdk::{api::TofnError, key::dummy_secret_recovery_key, wire_bytes},
+    };
+
+    #[test]
+    fn collect_aggregate_verify_quorum() {
+        let message_digest = [42; 32].into();
+
+        let key_pairs: Vec<_> = (0..4)
+            .map(|i| ecdsa::keygen(&dummy_secret_recovery_key(i), b"tofn nonce").unwrap())
+            .collect();
+        let encoded_verifying_keys: Vec<_> = key_pairs
+            .iter()
+            .map(|k| *k.encoded_verifying_key())
+            .collect();
+
+        let mut group = MultisigGroup::new(encoded_verifying_keys.clone(), message_digest).unwrap();
+
+        // add signatures out of order
+        for i in [3, 1] {
+            let signature = ecdsa::sign(key_pairs[i].signing_key(), &[42; 32].into()).unwrap();
+            group
+                .add_signature(SignerIndex::from_usize(i), &signature)
+                .unwrap();
+        }
+        assert_eq!(group.signature_count(), 2);
+
+        // duplicate signer, wrong signer and out-of-range signer are rejected
+        let signature = ecdsa::sign(key_pairs[1].signing_key(), &[42; 32].into()).unwrap();
+        assert_eq!(
+            group.add_signature(SignerIndex::from_usize(1), &signature),
+            Err(TofnError::InvalidArgument {
+                reason: "signer already signed"
+            })
+        );
+        assert_eq!(
+            group.add_signature(SignerIndex::from_usize(0), &signature),
+            Err(TofnError::InvalidSignature { index: 0 })
+        );
+        assert_eq!(
+            group.add_signature(SignerIndex::from_usize(4), &signature),
+            Err(TofnError::BadSignerIndex { index: 4 })
+        );
+        assert_eq!(group.signature_count(), 2);
+
+        let aggregate = group.aggregate().unwrap();
+
+        assert!(verify_quorum(&encoded_verifying_keys, 2, &[42; 32].into(), &aggregate).unwrap());
+        assert!(!verify_quorum(&encoded_verifying_keys, 3, &[42; 32].into(), &aggregate).unwrap());
+        assert!(!verify_quorum(&encoded_verifying_keys, 2, &[43; 32].into(), &aggregate).unwrap());
+
+        // zero quorum is rejected, even for an aggregate without signatures
+        let empty_aggregate = MultisigGroup::new(encoded_verifying_keys.clone(), [42; 32].into())
+            .unwrap()
+            .aggregate()
+            .unwrap();
+        for aggregate in [&aggregate, &empty_aggregate] {
+            assert_eq!(
+                verify_quorum(&encoded_verifying_keys, 0, &[42; 32].into(), aggregate),
+                Err(TofnError::InvalidArgument {
+                    reason: "zero quorum"
+                })
+            );
+        }
+        assert_eq!(
+            verify_quorum(&[], 0, &[42; 32].into(), &empty_aggregate),
+            Err(TofnError::InvalidArgument {
+                reason: "zero quorum"
+            })
+        );
+
+        // a repeated key would let one signer count twice towards the quorum
+        let signature = ecdsa::sign(key_pairs[0].signing_key(), &[42; 32].into()).unwrap();
+        let duplicate_keys = [
+            encoded_verifying_keys[0],
+            encoded_verifying_keys[0],
+            encoded_verifying_keys[1],
+        ];
+        let duplicate_aggregate = wire_bytes::serialize(&AggregateSignature {
+            signatures: (0..2)
+                .map(|i| {
+                    (
+                        SignerIndex::from_usize(i),
+                        k256_serde::Signature::from_bytes(&signature).unwrap(),
+                    )
+                })
+                .collect(),
+        })
+        .unwrap();
+        assert_eq!(
+            verify_quorum(&duplicate_keys, 2, &[42; 32].into(), &duplicate_aggregate),
+            Err(TofnError::InvalidArgument {
+                reason: "duplicate verifying key"
+            })
+        );
+
+        // signer indices refer to the wrong keys if the key list is reordered
+        let mut reordered_keys = encoded_verifying_keys.clone();
+        reordered_keys.swap(1, 2);
+        assert!(!verify_quorum(&reordered_keys, 2, &[42; 32].into(), &aggregate).unwrap());
+    }
+
+    #[test]
+    fn invalid_group() {
+        let key_pair = ecdsa::keygen(&dummy_secret_recovery_key(0), b"tofn nonce").unwrap();
+        let key = *key_pair.encoded_verifying_key();
+
+        assert!(MultisigGroup::new(vec![], [42; 32].into()).is_err());
+        assert!(MultisigGroup::new(vec![key, key], [42; 32].into()).is_err());
+        assert!(MultisigGroup::new(vec![key, [0; 33]], [42; 32].into()).is_err());
+    }
+}
@@ -0,0 +1,269 @@
+//! Scheme-agnostic keygen, sign and verify that dispatch to the individual scheme modules.
+
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::sdk::{
+    api::{BytesVec, MessageDigest, TofnError, TofnResult},
+    key::SecretRecoveryKey,
+};
+
+/// All signature schemes supported by tofn.
+/// Variants are present regardless of enabled features so that the serialized encoding is stable;
+/// using a scheme whose feature is disabled returns an error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SignatureScheme {
+    Ecdsa,
+    Ed25519,
+    Schnorr,
+    Sr25519,
+    Bls,
+}
+
+pub enum KeyPair {
+    #[cfg(feature = "secp256k1")]
+    Ecdsa(crate::ecdsa::KeyPair),
+    #[cfg(feature = "ed25519")]
+    Ed25519(crate::ed25519::KeyPair),
+    #[cfg(feature = "schnorr")]
+    Schnorr(crate::schnorr::KeyPair),
+    #[cfg(feature = "sr25519")]
+    Sr25519(crate::sr25519::KeyPair),
+    #[cfg(feature = "bls")]
+    Bls(crate::bls::KeyPair),
+}
+
+/// Only the scheme and verifying key are displayed, so that no variant can leak its signing key.
+impl core::fmt::Debug for KeyPair {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KeyPair")
+            .field("scheme", &self.scheme())
+            .field("encoded_verifying_key", &self.encoded_verifying_key())
+            .finish_non_exhaustive()
+    }
+}
+
+impl KeyPair {
+    pub fn scheme(&self) -> SignatureScheme {
+        match self {
+            #[cfg(feature = "secp256k1")]
+            Self::Ecdsa(_) => SignatureScheme::Ecdsa,
+            #[cfg(feature = "ed25519")]
+            Self::Ed25519(_) => SignatureScheme::Ed25519,
+            #[cfg(feature = "schnorr")]
+            Self::Schnorr(_) => SignatureScheme::Schnorr,
+            #[cfg(feature = "sr25519")]
+            Self::Sr25519(_) => SignatureScheme::Sr25519,
+            #[cfg(feature = "bls")]
+            Self::Bls(_) => SignatureScheme::Bls,
+        }
+    }
+
+    /// The verifying key in the encoding used by the `encoded_verifying_key` of the scheme module.
+    pub fn encoded_verifying_key(&self) -> BytesVec {
+        match self {
+            #[cfg(feature = "secp256k1")]
+            Self::Ecdsa(key_pair) => key_pair.encoded_verifying_key().to_vec(),
+            #[cfg(feature = "ed25519")]
+            Self::Ed25519(key_pair) => key_pair.encoded_verifying_key().to_vec(),
+            #[cfg(feature = "schnorr")]
+            Self::Schnorr(key_pair) => key_pair.encoded_verifying_key().to_vec(),
+            #[cfg(feature = "sr25519")]
+            Self::Sr25519(key_pair) => key_pair.encoded_verifying_key().to_vec(),
+            #[cfg(feature = "bls")]
+            Self::Bls(key_pair) => key_pair.encoded_verifying_key().to_vec(),
+        }
+    }
+}
+
+pub fn keygen(
+    scheme: SignatureScheme,
+    secret_recovery_key: &SecretRecoveryKey,
+    session_nonce: &[u8],
+) -> TofnResult<KeyPair> {
+    match scheme {
+        #[cfg(feature = "secp256k1")]
+        SignatureScheme::Ecdsa => {
+            crate::ecdsa::keygen(secret_recovery_key, session_nonce).map(KeyPair::Ecdsa)
+        }
+        #[cfg(feature = "ed25519")]
+        SignatureScheme::Ed25519 => {
+            crate::ed25519::keygen(secret_recovery_key, session_nonce).map(KeyPair::Ed25519)
+        }
+        #[cfg(feature = "schnorr")]
+        SignatureScheme::Schnorr => {
+            crate::schnorr::keygen(secret_recovery_key, session_nonce).map(KeyPair::Schnorr)
+        }
+        #[cfg(feature = "sr25519")]
+        SignatureScheme::Sr25519 => {
+            crate::sr25519::keygen(secret_recovery_key, session_nonce).map(KeyPair::Sr25519)
+        }
+        #[cfg(feature = "bls")]
+        SignatureScheme::Bls => {
+            crate::bls::keygen(secret_recovery_key, session_nonce).map(KeyPair::Bls)
+        }
+        #[allow(unreachable_patterns)]
+        _ => Err(unsupported(scheme)),
+    }
+}
+
+/// Sign with the scheme of `key_pair`.
+/// The signature encoding is that of the `sign` function of the scheme module.
+pub fn sign(key_pair: &KeyPair, message_digest: &MessageDigest) -> TofnResult<BytesVec> {
+    match key_pair {
+        #[cfg(feature = "secp256k1")]
+        KeyPair::Ecdsa(key_pair) => crate::ecdsa::sign(key_pair.signing_key(), message_digest),
+        #[cfg(feature = "ed25519")]
+        KeyPair::Ed25519(key_pair) => crate::ed25519::sign(key_pair, message_digest),
+        #[cfg(feature = "schnorr")]
+        KeyPair::Schnorr(key_pair) => crate::schnorr::sign(key_pair, message_digest),
+        #[cfg(feature = "sr25519")]
+        KeyPair::Sr25519(key_pair) => crate::sr25519::sign(key_pair, message_digest),
+        #[cfg(feature = "bls")]
+        KeyPair::Bls(key_pair) => crate::bls::sign(key_pair, message_digest),
+    }
+}
+
+/// Verify a signature produced by [sign].
+/// Returns an error if `encoded_verifying_key` has the wrong length for `scheme`.
+pub fn verify(
+    scheme: SignatureScheme,
+    encoded_verifying_key: &[u8],
+    message_digest: &MessageDigest,
+    encoded_signature: &[u8],
+) -> TofnResult<bool> {
+    match scheme {
+        #[cfg(feature = "secp256k1")]
+        SignatureScheme::Ecdsa => crate::ecdsa::verify(
+            &to_array(encoded_verifying_key)?,
+            message_digest,
+            encoded_signature,
+        ),
+        #[cfg(feature = "ed25519")]
+        SignatureScheme::Ed25519 => crate::ed25519::verify(
+            &to_array(encoded_verifying_key)?,
+            message_digest,
+            encoded_signature,
+        ),
+        #[cfg(feature = "schnorr")]
+        SignatureScheme::Schnorr => crate::schnorr::verify(
+            &to_array(encoded_verifying_key)?,
+            message_digest,
+            encoded_signature,
+        ),
+        #[cfg(feature = "sr25519")]
+        SignatureScheme::Sr25519 => crate::sr25519::verify(
+            &to_array(encoded_verifying_key)?,
+            message_digest,
+            encoded_signature,
+        ),
+        #[cfg(feature = "bls")]
+        SignatureScheme::Bls => crate::bls::verify(
+            &to_array(encoded_verifying_key)?,
+            message_digest,
+            encoded_signature,
+        ),
+        #[allow(unreachable_patterns)]
+        _ => Err(unsupported(scheme)),
+    }
+}
+
+fn to_array<const N: usize>(bytes: &[u8]) -> TofnResult<[u8; N]> {
+    bytes.try_into().map_err(|_| {
+        error!(
+            "invalid verifying key length {}, expected {}",
+            bytes.len(),
+            N
+        );
+        TofnError::InvalidEncoding {
+            kind: "verifying key",
+        }
+    })
+}
+
+fn unsupported(scheme: SignatureScheme) -> TofnError {
+    error!("signature scheme {:?} is not enabled", scheme);
+    TofnError::UnsupportedScheme
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{keygen, sign, verify, SignatureScheme};
+    use crate::sdk::{
+        api::{MessageDigest, TofnError},
+        key::dummy_secret_recovery_key,
+    };
+
+    const ENABLED_SCHEMES: &[SignatureScheme] = &[
+        #[cfg(feature = "secp256k1")]
+        SignatureScheme::Ecdsa,
+        #[cfg(feature = "ed25519")]
+        SignatureScheme::Ed25519,
+        #[cfg(feature = "schnorr")]
+        SignatureScheme::Schnorr,
+        #[cfg(feature = "sr25519")]
+        SignatureScheme::Sr25519,
+        #[cfg(feature = "bls")]
+        SignatureScheme::Bls,
+    ];
+
+    #[test]
+    fn keygen_sign_verify_all_schemes() {
+        let message_digest = MessageDigest::from([42; 32]);
+
+        for &scheme in ENABLED_SCHEMES {
+            let key_pair = keygen(scheme, &dummy_secret_recovery_key(42), b"tofn nonce").unwrap();
+            assert_eq!(key_pair.scheme(), scheme);
+
+            let encoded_verifying_key = key_pair.encoded_verifying_key();
+            assert_eq!(
+                format!("{:?}", key_pair),
+                format!(
+                    "KeyPair {{ scheme: {:?}, encoded_verifying_key: {:?}, .. }}",
+                    scheme, encoded_verifying_key
+                )
+            );
+
+            let encoded_signature = sign(&key_pair, &message_digest).unwrap();
+
+            assert!(verify(
+                scheme,
+                &encoded_verifying_key,
+                &message_digest,
+                &encoded_signature
+            )
+            .unwrap());
+            assert!(!verify(
+                scheme,
+                &encoded_verifying_key,
+                &[43; 32].into(),
+                &encoded_signature
+            )
+            .unwrap_or(false));
+
+            // verifying key of the wrong length
+            assert_eq!(
+                verify(
+                    scheme,
+                    &encoded_verifying_key[1..],
+                    &message_digest,
+                    &encoded_signature
+                ),
+                Err(TofnError::InvalidEncoding {
+                    kind: "verifying key"
+                })
+            );
+
+            // signatures do not verify under any other scheme
+            for &other_scheme in ENABLED_SCHEMES.iter().filter(|&&s| s != scheme) {
+                assert!(!verify(
+                    other_scheme,
+                    &encoded_verifying_key,
+                    &message_digest,
+                    &encoded_signature
+                )
+                .unwrap_or(false));
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use k256::schnorr::{Signature, SigningKey, VerifyingKey};
+use rand::RngCore;
+use tracing::error;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{
+    constants::{KEYGEN_TAG, SCHNORR_TAG},
+    crypto_tools::{message_digest::MessageDigest, rng},
+    sdk::{
+        api::{BytesVec, TofnError, TofnResult},
+        key::SecretRecoveryKey,
+    },
+};
+
+#[derive(ZeroizeOnDrop)]
+pub struct KeyPair(SigningKey);
+
+/// `SigningKey` does not impl `Debug`, so only the verifying key is displayed.
+impl core::fmt::Debug for KeyPair {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KeyPair")
+            .field("encoded_verifying_key", &self.encoded_verifying_key())
+            .finish_non_exhaustive()
+    }
+}
+
+impl KeyPair {
+    /// BIP340 x-only encoding of the verifying key.
+    pub fn encoded_verifying_key(&self) -> [u8; 32] {
+        self.0.verifying_key().to_bytes().into()
+    }
+}
+
+pub fn keygen(
+    secret_recovery_key: &SecretRecoveryKey,
+    session_nonce: &[u8],
+) -> TofnResult<KeyPair> {
+    let mut rng =
+        rng::rng_seed_signing_key(SCHNORR_TAG, KEYGEN_TAG, secret_recovery_key, session_nonce)?;
+
+    let signing_key = SigningKey::random(&mut rng);
+
+    Ok(KeyPair(signing_key))
+}
+
+/// Returns a BIP340 Schnorr signature.
+/// The signature is encoded raw (R x-coordinate and S bytes) as a 64-byte array as per [BIP340](https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki#default-signing)
+///
+/// The auxiliary randomness is derived deterministically from the signing key and message digest,
+/// so signing the same digest twice yields the same signature.
+pub fn sign(signing_key: &KeyPair, message_digest: &MessageDigest) -> TofnResult<BytesVec> {
+    let mut signing_key_bytes: [u8; 32] = signing_key.0.to_bytes().into();
+
+    let rng = rng::rng_seed_signature(SCHNORR_TAG, SIGN_TAG, &signing_key_bytes, message_digest);
+
+    signing_key_bytes.zeroize();
+
+    let mut rng = rng?;
+    let mut aux_rand = [0; 32];
+    rng.fill_bytes(&mut aux_rand);
+
+    let result = signing_key.0.sign_raw(message_digest.as_ref(), &aux_rand);
+
+    aux_rand.zeroize();
+
+    Ok(result
+        .map_err(|_| {
+            error!("failure to sign");
+            TofnError::Internal {
+                reason: "failure to sign",
+            }
+        })?
+        .to_bytes()
+        .into())
+}
+
+pub fn verify(
+    encoded_verifying_key: &[u8; 32],
+    message_digest: &MessageDigest,
+    encoded_signature: &[u8],
+) -> TofnResult<bool> {
+    let verifying_key = VerifyingKey::from_bytes(encoded_verifying_key).map_err(|_| {
+        TofnError::InvalidEncoding {
+            kind: "schnorr verifying key",
+        }
+    })?;
+
+    let signature =
+        Signature::try_from(encoded_signature).map_err(|_| TofnError::InvalidEncoding {
+            kind: "schnorr signature",
+        })?;
+
+    Ok(verifying_key
+        .verify_raw(message_digest.as_ref(), &signature)
+        .is_ok())
+}
+
+/// Domain separation for seeding the RNG
+const SIGN_TAG: u8 = 0x01;
+
+#[cfg(test)]
+mod tests {
+    use super::{keygen, sign, verify};
+    use crate::sdk::key::{dummy_secret_recovery_key, SecretRecoveryKey};
+
+    #[test]
+    fn keygen_sign_decode_verify() {
+        let message_digest = [42; 32].into();
+
+        let key_pair = keygen(&dummy_secret_recovery_key(42), b"tofn nonce").unwrap();
+        let mut encoded_signature = sign(&key_pair, &message_digest).unwrap();
+
+        // Correct signature should verify
+        let success = verify(
+            &key_pair.encoded_verifying_key(),
+            &message_digest,
+            &encoded_signature,
+        )
+        .unwrap();
+
+        assert!(success);
+
+        // Tamper with the signature, it should no longer verify.
+        *encoded_signature.last_mut().unwrap() += 1;
+
+        let success = verify(
+            &key_pair.encoded_verifying_key(),
+            &message_digest,
+            &encoded_signature,
+        )
+        .unwrap();
+
+        assert!(!success);
+    }
+
+    /// Test vector 1 from <https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv>
+    #[test]
+    fn verify_bip340_vector() {
+        let encoded_verifying_key: [u8; 32] =
+            hex::decode("DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let message_digest: [u8; 32] =
+            hex::decode("243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89")
+                .unwrap()
+                .try_into()
+                .unwrap();
+        let encoded_signature = hex::decode("6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A").unwrap();
+
+        assert!(verify(
+            &encoded_verifying_key,
+            &message_digest.into(),
+            &encoded_signature
+        )
+        .unwrap());
+    }
+
+    /// Check keygen/signing outputs against golden files to catch regressions (such as on updating deps).
+    #[test]
+    fn keygen_sign_known_vectors() {
+        struct TestCase {
+            secret_recovery_key: SecretRecoveryKey,
+            session_nonce: Vec<u8>,
+            message_digest: [u8; 32],
+        }
+
+        let test_cases = vec![
+            TestCase {
+                secret_recovery_key: SecretRecoveryKey([0; 64]),
+                session_nonce: vec![0; 4],
+                message_digest: [42; 32],
+            },
+            TestCase {
+                secret_recovery_key: SecretRecoveryKey([0xff; 64]),
+                session_nonce: vec![0xff; 32],
+                message_digest: [0xff; 32],
+            },
+        ];
+
+        let expected_outputs: Vec<Vec<_>> = test_cases
+            .into_iter()
+            .map(|test_case| {
+                let keypair =
+                    keygen(&test_case.secret_recovery_key, &test_case.session_nonce).unwrap();
+                let encoded_signing_key = keypair.0.to_bytes().to_vec();
+                let encoded_verifying_key = keypair.encoded_verifying_key().to_vec();
+
+                let signature = sign(&keypair, &test_case.message_digest.into()).unwrap();
+
+                let success = verify(
+                    &keypair.encoded_verifying_key(),
+                    &test_case.message_digest.into(),
+                    &signature,
+                )
+                .unwrap();
+                assert!(success);
+
+                [encoded_signing_key, encoded_verifying_key, signature]
+                    .into_iter()
+                    .map(hex::encode)
+                    .collect()
+            })
+            .collect();
+
+        goldie::assert_json!(expected_outputs);
+    }
+}
@@ -1,14 +1,66 @@
 //! API for tofn users
-pub type TofnResult<T> = Result<T, TofnFatal>;
-pub type BytesVec = Vec<u8>;
+use core::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct TofnFatal;
+pub type TofnResult<T> = Result<T, TofnError>;
+pub type BytesVec = alloc::vec::Vec<u8>;
+
+/// Errors returned by tofn.
+/// Details beyond those carried by the variant are logged via `tracing` where the error occurs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TofnError {
+    /// Serialization of a value failed.
+    Serialization,
+    /// An argument is outside its allowed range or inconsistent with other arguments.
+    InvalidArgument { reason: &'static str },
+    /// An encoded key, signature or proof could not be decoded.
+    InvalidEncoding { kind: &'static str },
+    /// A signer index is out of range.
+    BadSignerIndex { index: usize },
+    /// A signature from the given signer does not verify.
+    InvalidSignature { index: usize },
+    /// The signature scheme is not enabled by the crate features.
+    UnsupportedScheme,
+    /// A deterministic RNG could not be seeded.
+    RngFailure,
+    /// An underlying cryptographic primitive failed unexpectedly.
+    Internal { reason: &'static str },
+}
+
+impl fmt::Display for TofnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Serialization => write!(f, "serialization failure"),
+            Self::InvalidArgument { reason } => write!(f, "invalid argument: {}", reason),
+            Self::InvalidEncoding { kind } => write!(f, "invalid {} encoding", kind),
+            Self::BadSignerIndex { index } => write!(f, "bad signer index {}", index),
+            Self::InvalidSignature { index } => {
+                write!(f, "invalid signature from signer {}", index)
+            }
+            Self::UnsupportedScheme => write!(f, "signature scheme is not enabled"),
+            Self::RngFailure => write!(f, "failure to seed rng"),
+            Self::Internal { reason } => write!(f, "internal error: {}", reason),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TofnError {}
 
 /// Expose tofn's (de)serialization functions
 /// that use the appropriate bincode config options.
+#[cfg(feature = "std")]
 pub use super::wire_bytes::{deserialize, serialize};
 
 pub use super::key::SecretRecoveryKey;
 
-pub use crate::crypto_tools::message_digest::MessageDigest;
+pub use crate::crypto_tools::message_digest::{HashScheme, MessageDigest};
+
+/// Expose tofn's deterministic key derivation so it can be reproduced externally.
+#[cfg(any(
+    feature = "secp256k1",
+    feature = "ed25519",
+    feature = "sr25519",
+    feature = "bls"
+))]
+pub use crate::crypto_tools::rng::{derive_signing_key, rng_seed_signing_key};
@@ -1,11 +1,17 @@
-use std::{
+use core::{
     array::TryFromSliceError,
     convert::{TryFrom, TryInto},
 };
-use zeroize::Zeroize;
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
-#[derive(Debug, Clone, Zeroize)]
-#[zeroize(drop)]
+#[cfg(feature = "bip39")]
+use super::api::{TofnError, TofnResult};
+#[cfg(feature = "bip39")]
+use bip39::{Language, Mnemonic};
+#[cfg(feature = "bip39")]
+use tracing::error;
+
+#[derive(Debug, Clone, Zeroize, ZeroizeOnDrop)]
 pub struct SecretRecoveryKey(pub(crate) [u8; 64]);
 
 impl TryFrom<&[u8]> for SecretRecoveryKey {
@@ -16,6 +22,40 @@ impl TryFrom<&[u8]> for SecretRecoveryKey {
     }
 }
 
+#[cfg(feature = "bip39")]
+const MNEMONIC_WORD_COUNT: usize = 24;
+
+#[cfg(feature = "bip39")]
+impl SecretRecoveryKey {
+    /// Use the 64-byte [BIP39](https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki) seed
+    /// of a 24-word English mnemonic and `passphrase` as the recovery key.
+    /// Fails if the mnemonic has a different number of words, unknown words or an invalid checksum.
+    ///
+    /// There is no inverse `to_mnemonic`, since the seed is a one-way (PBKDF2) function of the mnemonic.
+    /// Back up the mnemonic itself rather than the recovery key.
+    pub fn from_mnemonic(phrase: &str, passphrase: &str) -> TofnResult<Self> {
+        let mnemonic = Mnemonic::parse_in(Language::English, phrase).map_err(|err| {
+            error!("invalid mnemonic: {}", err);
+            TofnError::InvalidArgument {
+                reason: "invalid mnemonic",
+            }
+        })?;
+
+        if mnemonic.word_count() != MNEMONIC_WORD_COUNT {
+            error!(
+                "invalid mnemonic word count {}, expected {}",
+                mnemonic.word_count(),
+                MNEMONIC_WORD_COUNT
+            );
+            return Err(TofnError::InvalidArgument {
+                reason: "mnemonic word count",
+            });
+        }
+
+        Ok(Self(mnemonic.to_seed(passphrase)))
+    }
+}
+
 #[cfg(test)]
 /// return the all-zero array with the first bytes set to the bytes of `index`
 pub fn dummy_secret_recovery_key(index: usize) -> SecretRecoveryKey {
@@ -26,3 +66,63 @@ pub fn dummy_secret_recovery_key(index: usize) -> SecretRecoveryKey {
     }
     SecretRecoveryKey(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use zeroize::ZeroizeOnDrop;
+
+    fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+
+    /// Compile-time check that all types holding secret key material are wiped on drop.
+    #[test]
+    fn secrets_zeroize_on_drop() {
+        assert_zeroize_on_drop::<super::SecretRecoveryKey>();
+
+        #[cfg(feature = "secp256k1")]
+        {
+            assert_zeroize_on_drop::<crate::crypto_tools::k256_serde::SecretScalar>();
+            assert_zeroize_on_drop::<crate::ecdsa::KeyPair>();
+        }
+
+        #[cfg(feature = "ed25519")]
+        assert_zeroize_on_drop::<crate::ed25519::KeyPair>();
+
+        #[cfg(feature = "schnorr")]
+        assert_zeroize_on_drop::<crate::schnorr::KeyPair>();
+
+        #[cfg(feature = "sr25519")]
+        assert_zeroize_on_drop::<crate::sr25519::KeyPair>();
+
+        #[cfg(feature = "bls")]
+        assert_zeroize_on_drop::<crate::bls::KeyPair>();
+    }
+
+    /// Test vector from <https://github.com/trezor/python-mnemonic/blob/master/vectors.json>
+    #[cfg(feature = "bip39")]
+    #[test]
+    fn from_mnemonic() {
+        use super::SecretRecoveryKey;
+
+        let phrase = [["abandon"; 23].join(" ").as_str(), "art"].join(" ");
+
+        let secret_recovery_key = SecretRecoveryKey::from_mnemonic(&phrase, "TREZOR").unwrap();
+        assert_eq!(
+            hex::encode(secret_recovery_key.0),
+            "bda85446c68413707090a52022edd26a1c9462295029f2e60cd7c4f2bbd3097170af7a4d73245cafa9c3cca8d561a7c3de6f5d4a10be8ed2a5e608d68f92fcc8"
+        );
+
+        // the passphrase changes the recovery key
+        assert_ne!(
+            SecretRecoveryKey::from_mnemonic(&phrase, "").unwrap().0,
+            secret_recovery_key.0
+        );
+
+        // invalid checksum
+        let phrase = ["abandon"; 24].join(" ");
+        assert!(SecretRecoveryKey::from_mnemonic(&phrase, "TREZOR").is_err());
+
+        // valid mnemonic, but only 12 words
+        let phrase = [["abandon"; 11].join(" ").as_str(), "about"].join(" ");
+        assert!(SecretRecoveryKey::from_mnemonic(&phrase, "TREZOR").is_err());
+    }
+}
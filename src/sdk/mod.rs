@@ -2,4 +2,5 @@ pub mod api;
 
 pub(crate) mod key;
 
+#[cfg(feature = "std")]
 pub(crate) mod wire_bytes;
@@ -1,4 +1,4 @@
-use crate::sdk::api::TofnFatal;
+use crate::sdk::api::TofnError;
 use serde::{de::DeserializeOwned, Serialize};
 use tracing::{error, warn};
 
@@ -20,7 +20,7 @@ pub fn serialize<T: ?Sized + Serialize>(value: &T) -> TofnResult<BytesVec> {
 
     bincode.serialize(value).map_err(|err| {
         error!("serialization failure: {}", err.to_string());
-        TofnFatal
+        TofnError::Serialization
     })
 }
 
@@ -0,0 +1,178 @@
+use schnorrkel::{
+    context::attach_rng, signing_context, ExpansionMode, Keypair, MiniSecretKey, PublicKey,
+    Signature, PUBLIC_KEY_LENGTH,
+};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::{
+    constants::{KEYGEN_TAG, SR25519_TAG},
+    crypto_tools::{message_digest::MessageDigest, rng},
+    sdk::{
+        api::{BytesVec, TofnError, TofnResult},
+        key::SecretRecoveryKey,
+    },
+};
+
+/// Signing context used by Substrate for all sr25519 signatures.
+const SIGNING_CONTEXT: &[u8] = b"substrate";
+
+#[derive(ZeroizeOnDrop)]
+pub struct KeyPair(Keypair);
+
+/// `Keypair`'s own `Debug` impl prints the secret key, so only the verifying key is displayed.
+impl core::fmt::Debug for KeyPair {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("KeyPair")
+            .field("encoded_verifying_key", &self.encoded_verifying_key())
+            .finish_non_exhaustive()
+    }
+}
+
+impl KeyPair {
+    pub fn encoded_verifying_key(&self) -> [u8; PUBLIC_KEY_LENGTH] {
+        self.0.public.to_bytes()
+    }
+}
+
+/// The signing key is expanded from a 32-byte mini secret key
+/// the same way Substrate does (`ExpansionMode::Ed25519`).
+pub fn keygen(
+    secret_recovery_key: &SecretRecoveryKey,
+    session_nonce: &[u8],
+) -> TofnResult<KeyPair> {
+    let mut rng =
+        rng::rng_seed_signing_key(SR25519_TAG, KEYGEN_TAG, secret_recovery_key, session_nonce)?;
+
+    let mut mini_secret_key = MiniSecretKey::generate_with(&mut rng);
+    let key_pair = mini_secret_key.expand_to_keypair(ExpansionMode::Ed25519);
+
+    mini_secret_key.zeroize();
+
+    Ok(KeyPair(key_pair))
+}
+
+/// Returns a 64-byte sr25519 signature over `message_digest` in the Substrate signing context.
+/// The witness randomness is derived deterministically from the signing key and message digest.
+pub fn sign(signing_key: &KeyPair, message_digest: &MessageDigest) -> TofnResult<BytesVec> {
+    let mut secret_key_bytes = signing_key.0.secret.to_bytes();
+
+    let rng = rng::rng_seed_signature(SR25519_TAG, SIGN_TAG, &secret_key_bytes, message_digest);
+
+    secret_key_bytes.zeroize();
+
+    let transcript = attach_rng(
+        signing_context(SIGNING_CONTEXT).bytes(message_digest.as_ref()),
+        rng?,
+    );
+
+    Ok(signing_key.0.sign(transcript).to_bytes().into())
+}
+
+pub fn verify(
+    encoded_verifying_key: &[u8; PUBLIC_KEY_LENGTH],
+    message_digest: &MessageDigest,
+    encoded_signature: &[u8],
+) -> TofnResult<bool> {
+    let verifying_key =
+        PublicKey::from_bytes(encoded_verifying_key).map_err(|_| TofnError::InvalidEncoding {
+            kind: "sr25519 verifying key",
+        })?;
+
+    let signature =
+        Signature::from_bytes(encoded_signature).map_err(|_| TofnError::InvalidEncoding {
+            kind: "sr25519 signature",
+        })?;
+
+    Ok(verifying_key
+        .verify_simple(SIGNING_CONTEXT, message_digest.as_ref(), &signature)
+        .is_ok())
+}
+
+/// Domain separation for seeding the RNG
+const SIGN_TAG: u8 = 0x01;
+
+#[cfg(test)]
+mod tests {
+    use super::{keygen, sign, verify};
+    use crate::sdk::key::{dummy_secret_recovery_key, SecretRecoveryKey};
+
+    #[test]
+    fn keygen_sign_decode_verify() {
+        let message_digest = [42; 32].into();
+
+        let key_pair = keygen(&dummy_secret_recovery_key(42), b"tofn nonce").unwrap();
+        let mut encoded_signature = sign(&key_pair, &message_digest).unwrap();
+
+        // Correct signature should verify
+        let success = verify(
+            &key_pair.encoded_verifying_key(),
+            &message_digest,
+            &encoded_signature,
+        )
+        .unwrap();
+
+        assert!(success);
+
+        // Tamper with the signature, it should no longer verify.
+        encoded_signature[0] ^= 1;
+
+        let success = verify(
+            &key_pair.encoded_verifying_key(),
+            &message_digest,
+            &encoded_signature,
+        )
+        .unwrap();
+
+        assert!(!success);
+    }
+
+    /// Check keygen/signing outputs against golden files to catch regressions (such as on updating deps).
+    #[test]
+    fn keygen_sign_known_vectors() {
+        struct TestCase {
+            secret_recovery_key: SecretRecoveryKey,
+            session_nonce: Vec<u8>,
+            message_digest: [u8; 32],
+        }
+
+        let test_cases = vec![
+            TestCase {
+                secret_recovery_key: SecretRecoveryKey([0; 64]),
+                session_nonce: vec![0; 4],
+                message_digest: [42; 32],
+            },
+            TestCase {
+                secret_recovery_key: SecretRecoveryKey([0xff; 64]),
+                session_nonce: vec![0xff; 32],
+                message_digest: [0xff; 32],
+            },
+        ];
+
+        let expected_outputs: Vec<Vec<_>> = test_cases
+            .into_iter()
+            .map(|test_case| {
+                let keypair =
+                    keygen(&test_case.secret_recovery_key, &test_case.session_nonce).unwrap();
+                let encoded_signing_key = keypair.0.secret.to_bytes().to_vec();
+                let encoded_verifying_key = keypair.encoded_verifying_key().to_vec();
+
+                let signature = sign(&keypair, &test_case.message_digest.into()).unwrap();
+
+                let success = verify(
+                    &keypair.encoded_verifying_key(),
+                    &test_case.message_digest.into(),
+                    &signature,
+                )
+                .unwrap();
+                assert!(success);
+
+                [encoded_signing_key, encoded_verifying_key, signature]
+                    .into_iter()
+                    .map(hex::encode)
+                    .collect()
+            })
+            .collect();
+
+        goldie::assert_json!(expected_outputs);
+    }
+}